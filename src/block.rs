@@ -0,0 +1,314 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Block elements: headings, rules, indentation and paragraphs.
+//!
+//! Block elements are laid out in a width (80 columns, unless painted with an explicit one or
+//! with the terminal's) and are painted as whole lines, each ending with a newline.
+
+use std_prelude::*;
+
+use layout::{self, repeat, Line};
+use {El, Style, Text, Wrap};
+
+/// The width to lay out blocks in when none was given.
+///
+/// This is fixed (rather than the terminal's width) so that painting doesn't depend on the
+/// environment, `paint_term` fits the terminal instead.
+pub(crate) const DEFAULT_WIDTH: usize = 80;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// A horizontal line across the full width.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let els = vec![
+///     El::plain("above\n".into()),
+///     El::Rule(Rule::new().c('=')),
+///     El::plain("below\n".into()),
+/// ];
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &els, 10).unwrap();
+/// assert_eq!("above\n==========\nbelow\n", String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Rule {
+    c: char,
+    style: Style,
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule {
+            c: '─',
+            style: Style::new(),
+        }
+    }
+}
+
+impl Rule {
+    /// Create a rule drawn with `─`.
+    pub fn new() -> Rule {
+        Rule::default()
+    }
+
+    /// Set the character the rule is drawn with.
+    pub fn c(mut self, c: char) -> Rule {
+        self.c = c;
+        self
+    }
+
+    /// Set the style of the rule.
+    pub fn style(mut self, style: Style) -> Rule {
+        self.style = style;
+        self
+    }
+
+    pub fn get_char(&self) -> char {
+        self.c
+    }
+
+    pub fn get_style(&self) -> Style {
+        self.style
+    }
+
+    /// Clears the style of the rule.
+    pub fn set_plain(&mut self) {
+        self.style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let width = width.unwrap_or(DEFAULT_WIDTH);
+        vec![vec![Text::new(repeat(self.c, width)).with_style(&self.style)]]
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// Text wrapped to the width it is painted in.
+///
+/// See [`Wrap`](struct.Wrap.html) for how the text is wrapped.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let els = vec![
+///     El::Paragraph(
+///         Paragraph::new(vec![Text::new("a paragraph of text which is wrapped".into())])
+///             .indent(2),
+///     ),
+/// ];
+///
+/// let expected = "  a paragraph of\ntext which is\nwrapped\n";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &els, 16).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Paragraph {
+    texts: Vec<Text>,
+    indent: usize,
+    hanging: usize,
+    justify: bool,
+}
+
+impl Paragraph {
+    pub fn new(texts: Vec<Text>) -> Paragraph {
+        Paragraph {
+            texts,
+            ..Paragraph::default()
+        }
+    }
+
+    /// Indent the first line by `indent` spaces.
+    pub fn indent(mut self, indent: usize) -> Paragraph {
+        self.indent = indent;
+        self
+    }
+
+    /// Indent the lines after the first by `hanging` spaces.
+    pub fn hanging(mut self, hanging: usize) -> Paragraph {
+        self.hanging = hanging;
+        self
+    }
+
+    /// Stretch the spaces between words so that the lines fill the width.
+    pub fn justify(mut self, justify: bool) -> Paragraph {
+        self.justify = justify;
+        self
+    }
+
+    pub fn get_texts(&self) -> &[Text] {
+        &self.texts
+    }
+
+    /// Clears the formatting of the text.
+    pub fn set_plain(&mut self) {
+        for t in &mut self.texts {
+            t.set_plain();
+        }
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        Wrap::new(width.unwrap_or(DEFAULT_WIDTH))
+            .indent(self.indent)
+            .hanging(self.hanging)
+            .justify(self.justify)
+            .lines(&self.texts)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// A heading, painted in bold (by default). Levels 1 and 2 are underlined with `═` and `─`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let mut els = vec![
+///     El::Heading(Heading::new(1, vec![Text::new("Title".into())])),
+///     El::Heading(Heading::new(3, vec![Text::new("Section".into())])),
+/// ];
+/// els[0].set_plain();
+/// els[1].set_plain();
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &els, 80).unwrap();
+/// assert_eq!("Title\n═════\nSection\n", String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Heading {
+    level: usize,
+    texts: Vec<Text>,
+    style: Style,
+}
+
+impl Heading {
+    pub fn new(level: usize, texts: Vec<Text>) -> Heading {
+        Heading {
+            level,
+            texts,
+            style: Style::new().bold(),
+        }
+    }
+
+    /// Set the style applied on top of the heading's text (bold by default).
+    pub fn style(mut self, style: Style) -> Heading {
+        self.style = style;
+        self
+    }
+
+    pub fn get_level(&self) -> usize {
+        self.level
+    }
+
+    pub fn get_texts(&self) -> &[Text] {
+        &self.texts
+    }
+
+    /// Clears the formatting of the heading, including its style.
+    pub fn set_plain(&mut self) {
+        for t in &mut self.texts {
+            t.set_plain();
+        }
+        self.style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let texts: Vec<Text> = self.texts
+            .iter()
+            .map(|t| t.clone().with_style(&self.style))
+            .collect();
+        let mut lines = Wrap::new(width.unwrap_or(DEFAULT_WIDTH)).lines(&texts);
+        let underline = match self.level {
+            0 | 1 => '═',
+            2 => '─',
+            _ => return lines,
+        };
+        let w = lines.iter().map(|l| layout::line_width(l)).max().unwrap_or(0);
+        lines.push(vec![Text::new(repeat(underline, w))]);
+        lines
+    }
+}
+
+/// The lines of the elements, indented by `indent` spaces.
+pub(crate) fn indent_lines(indent: usize, els: &[El], width: Option<usize>) -> Vec<Line> {
+    let width = width.unwrap_or(DEFAULT_WIDTH).saturating_sub(indent);
+    let mut lines = block_lines(els, Some(width));
+    for line in &mut lines {
+        if !line.is_empty() {
+            line.insert(0, Text::new(repeat(' ', indent)));
+        }
+    }
+    lines
+}
+
+/// Lay out the elements as lines.
+///
+/// Consecutive `El::Text`s are joined and split at each `\n`. Text which doesn't end with a `\n`
+/// still ends its line when followed by a block.
+pub(crate) fn block_lines(els: &[El], width: Option<usize>) -> Vec<Line> {
+    block_lines_with(els, None, |el| el.lines(width))
+}
+
+/// Lay out the elements as lines like `block_lines`, wrapping the text to `wrap` (if given) and
+/// using `render` to lay out the blocks.
+pub(crate) fn block_lines_with<F>(els: &[El], wrap: Option<usize>, render: F) -> Vec<Line>
+where
+    F: Fn(&El) -> Vec<Line>,
+{
+    let inline_lines = |texts: &[Text]| match wrap {
+        Some(width) => inline_lines(texts)
+            .iter()
+            .flat_map(|line| layout::wrap_line(line, width))
+            .collect(),
+        None => inline_lines(texts),
+    };
+    let mut lines = Vec::new();
+    let mut inline = Vec::new();
+    for el in els {
+        match *el {
+            El::Text(ref t) => inline.push(t.clone()),
+            _ => {
+                lines.extend(inline_lines(&inline));
+                inline.clear();
+                lines.extend(render(el));
+            }
+        }
+    }
+    lines.extend(inline_lines(&inline));
+    lines
+}
+
+/// Split the texts at each `\n`, ignoring the empty line after a final `\n`.
+pub(crate) fn inline_lines(texts: &[Text]) -> Vec<Line> {
+    if texts.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = layout::split_lines(texts);
+    let ends_with_newline = texts
+        .iter()
+        .rev()
+        .find(|t| !t.get_text().is_empty())
+        .map(|t| t.get_text().ends_with('\n'))
+        .unwrap_or(false);
+    if ends_with_newline {
+        lines.pop();
+    }
+    lines
+}
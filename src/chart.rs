@@ -0,0 +1,307 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Sparklines and bar charts.
+
+use std_prelude::*;
+
+use block::DEFAULT_WIDTH;
+use gauge::{total_bits, Gauge};
+use layout::{self, repeat, Line};
+use {Style, Text};
+
+/// The glyphs of a sparkline, from the lowest to the highest value.
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const SPARKS_ASCII: [char; 8] = ['_', '.', ',', '-', '=', '+', '*', '#'];
+
+#[derive(Debug, Clone, Default)]
+/// A line of glyphs for a series of values, i.e. `▁▂▃▅▇`.
+///
+/// NaN values are drawn as spaces. Use [`texts`](#method.texts) to put it in a table cell.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let values = [1.0, 2.0, 3.0, 5.0, 7.0, 8.0];
+/// let spark = Sparkline::new(values.to_vec());
+/// let ascii = Sparkline::new(values.to_vec()).ascii(true);
+///
+/// let mut result = Vec::new();
+/// paint(&mut result, &[El::Sparkline(spark), El::Sparkline(ascii)]).unwrap();
+/// assert_eq!("▁▂▃▅▇█\n_.,=*#\n", String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Sparkline {
+    values: Vec<f64>,
+    min: Option<f64>,
+    max: Option<f64>,
+    ascii: bool,
+    style: Style,
+}
+
+// Values are compared by their bits, so that NaN values are equal to themselves.
+impl PartialEq for Sparkline {
+    fn eq(&self, other: &Sparkline) -> bool {
+        self.values.len() == other.values.len()
+            && self.values
+                .iter()
+                .zip(&other.values)
+                .all(|(&a, &b)| total_bits(a) == total_bits(b))
+            && self.min.map(total_bits) == other.min.map(total_bits)
+            && self.max.map(total_bits) == other.max.map(total_bits)
+            && self.ascii == other.ascii
+            && self.style == other.style
+    }
+}
+
+impl Eq for Sparkline {}
+
+impl Sparkline {
+    pub fn new(values: Vec<f64>) -> Sparkline {
+        Sparkline {
+            values,
+            ..Sparkline::default()
+        }
+    }
+
+    /// Set the value drawn with the lowest glyph (default the lowest value).
+    pub fn min(mut self, min: f64) -> Sparkline {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the value drawn with the highest glyph (default the highest value).
+    pub fn max(mut self, max: f64) -> Sparkline {
+        self.max = Some(max);
+        self
+    }
+
+    /// Draw the values with ascii characters (`_.,-=+*#`).
+    pub fn ascii(mut self, ascii: bool) -> Sparkline {
+        self.ascii = ascii;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Sparkline {
+        self.style = style;
+        self
+    }
+
+    pub fn get_values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Clears the formatting of the sparkline.
+    pub fn set_plain(&mut self) {
+        self.style = Style::new();
+    }
+
+    /// The sparkline as texts, i.e. to put it in a table cell.
+    pub fn texts(&self) -> Vec<Text> {
+        let values = || self.values.iter().cloned().filter(|v| !v.is_nan());
+        let min = self.min.unwrap_or_else(|| values().fold(f64::INFINITY, f64::min));
+        let max = self.max.unwrap_or_else(|| values().fold(f64::NEG_INFINITY, f64::max));
+        let glyphs = if self.ascii { &SPARKS_ASCII } else { &SPARKS };
+        let top = (glyphs.len() - 1) as f64;
+        let spark = self.values
+            .iter()
+            .map(|&v| {
+                if v.is_nan() {
+                    ' '
+                } else if max > min {
+                    let level = ((v - min) / (max - min)).clamp(0.0, 1.0);
+                    glyphs[(level * top).round() as usize]
+                } else {
+                    glyphs[0]
+                }
+            })
+            .collect();
+        vec![Text::new(spark).with_style(&self.style)]
+    }
+
+    pub(crate) fn lines(&self) -> Vec<Line> {
+        vec![self.texts()]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// A horizontal bar chart: a labeled bar for each value, with the values lined up after them.
+///
+/// Bars are drawn with unicode blocks showing eighths of a column, or with `#` in ascii. When no
+/// bar width is set the bars fill the width.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let t = |s: &str| vec![Text::new(s.into())];
+/// let chart = BarChart::new()
+///     .bar(t("cpu"), 4.0)
+///     .bar(t("memory"), 12.5)
+///     .bar(t("disk"), 20.0)
+///     .ascii(true);
+///
+/// let expected = "\
+/// cpu    ####                    4
+/// memory #############        12.5
+/// disk   ####################   20
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::BarChart(chart)], 32).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct BarChart {
+    bars: Vec<(Vec<Text>, f64)>,
+    width: Option<usize>,
+    max: Option<f64>,
+    precision: Option<usize>,
+    ascii: bool,
+    style: Style,
+}
+
+// Values are compared by their bits, so that NaN values are equal to themselves.
+impl PartialEq for BarChart {
+    fn eq(&self, other: &BarChart) -> bool {
+        let same_bar = |a: &(Vec<Text>, f64), b: &(Vec<Text>, f64)| {
+            a.0 == b.0 && total_bits(a.1) == total_bits(b.1)
+        };
+        self.bars.len() == other.bars.len()
+            && self.bars.iter().zip(&other.bars).all(|(a, b)| same_bar(a, b))
+            && self.width == other.width
+            && self.max.map(total_bits) == other.max.map(total_bits)
+            && self.precision == other.precision
+            && self.ascii == other.ascii
+            && self.style == other.style
+    }
+}
+
+impl Eq for BarChart {}
+
+impl BarChart {
+    /// Create an empty chart, add the bars with `bar`.
+    pub fn new() -> BarChart {
+        BarChart::default()
+    }
+
+    /// Add a bar below the others.
+    pub fn bar(mut self, label: Vec<Text>, value: f64) -> BarChart {
+        self.bars.push((label, value));
+        self
+    }
+
+    /// Set the width of the bars, excluding the labels and values.
+    pub fn width(mut self, width: usize) -> BarChart {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the value of a full bar (default the highest value).
+    pub fn max(mut self, max: f64) -> BarChart {
+        self.max = Some(max);
+        self
+    }
+
+    /// Show the values with `precision` decimals.
+    pub fn precision(mut self, precision: usize) -> BarChart {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Draw the bars with `#`.
+    pub fn ascii(mut self, ascii: bool) -> BarChart {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Set the style of the bars.
+    pub fn style(mut self, style: Style) -> BarChart {
+        self.style = style;
+        self
+    }
+
+    pub fn get_bars(&self) -> &[(Vec<Text>, f64)] {
+        &self.bars
+    }
+
+    /// Clears the formatting of the labels and bars.
+    pub fn set_plain(&mut self) {
+        for t in self.bars.iter_mut().flat_map(|b| b.0.iter_mut()) {
+            t.set_plain();
+        }
+        self.style = Style::new();
+    }
+
+    /// The chart as texts, i.e. to put it in a table cell.
+    ///
+    /// The bars are 20 columns wide unless the width is set.
+    pub fn texts(&self) -> Vec<Text> {
+        let mut texts = Vec::new();
+        for line in self.chart_lines(None) {
+            if !texts.is_empty() {
+                texts.push(Text::new("\n".into()));
+            }
+            texts.extend(line);
+        }
+        texts
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        self.chart_lines(Some(width.unwrap_or(DEFAULT_WIDTH)))
+    }
+
+    /// The lines of the chart, with the bars filling `width` if it is given and no bar width is set.
+    fn chart_lines(&self, width: Option<usize>) -> Vec<Line> {
+        let values = self.bars
+            .iter()
+            .map(|b| match self.precision {
+                Some(p) => format!("{:.*}", p, b.1),
+                None => b.1.to_string(),
+            })
+            .collect::<Vec<_>>();
+        let label_width = self.bars
+            .iter()
+            .map(|b| layout::line_width(&b.0))
+            .max()
+            .unwrap_or(0);
+        let value_width = values.iter().map(|v| layout::str_width(v)).max().unwrap_or(0);
+        let bar_width = match (self.width, width) {
+            (Some(w), _) => w,
+            (None, Some(w)) => w.saturating_sub(label_width + value_width + 2).max(1),
+            (None, None) => 20,
+        };
+        let max = self.max.unwrap_or_else(|| {
+            self.bars.iter().map(|b| b.1).fold(0.0, f64::max)
+        });
+
+        let mut out = Vec::new();
+        for (&(ref label, value), v) in self.bars.iter().zip(values) {
+            let ratio = if max > 0.0 { value / max } else { 0.0 };
+            let bar = Gauge::new(ratio)
+                .width(bar_width)
+                .blocks(!self.ascii)
+                .empty(' ')
+                .brackets(false)
+                .percent(false)
+                .style(self.style);
+            let mut line = label.clone();
+            layout::pad_line(&mut line, label_width);
+            line.push(Text::new(" ".into()));
+            line.extend(bar.texts());
+            let pad = value_width - layout::str_width(&v);
+            line.push(Text::new(format!(" {}{}", repeat(' ', pad), v)));
+            out.push(line);
+        }
+        out
+    }
+}
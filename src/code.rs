@@ -0,0 +1,468 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Code blocks with syntax highlighting.
+
+use std_prelude::*;
+
+use block::{inline_lines, DEFAULT_WIDTH};
+use layout::{self, Line};
+use {Color, Style, Text};
+
+/// Highlights source code, i.e. for a [`Code`](struct.Code.html) block.
+///
+/// Implement this to use another highlighter than the built-in [`Lexer`](struct.Lexer.html).
+pub trait Highlighter {
+    /// Split the source into styled texts. Their text joined together must be the source.
+    fn highlight(&self, language: &str, source: &str) -> Vec<Text>;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// The kinds of tokens found by the [`Lexer`](struct.Lexer.html).
+pub enum Token {
+    Plain,
+    Key,
+    String,
+    Number,
+    Keyword,
+    Punctuation,
+    Comment,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// The style of each kind of [`Token`](enum.Token.html).
+///
+/// Uses the names of the tokens in lowercase when deserialized, i.e. `{key: {c: blue}}`.
+pub struct CodeTheme {
+    key: Style,
+    string: Style,
+    number: Style,
+    keyword: Style,
+    punctuation: Style,
+    comment: Style,
+}
+
+impl Default for CodeTheme {
+    fn default() -> CodeTheme {
+        CodeTheme {
+            key: Style::new().color(Color::Blue),
+            string: Style::new().color(Color::Green),
+            number: Style::new().color(Color::Cyan),
+            keyword: Style::new().color(Color::Purple),
+            punctuation: Style::new(),
+            comment: Style::new().dim(),
+        }
+    }
+}
+
+impl CodeTheme {
+    /// A theme without any styles.
+    pub fn plain() -> CodeTheme {
+        CodeTheme {
+            key: Style::new(),
+            string: Style::new(),
+            number: Style::new(),
+            keyword: Style::new(),
+            punctuation: Style::new(),
+            comment: Style::new(),
+        }
+    }
+
+    /// Set the style of a kind of token. The style of `Token::Plain` can not be set.
+    pub fn token(mut self, token: Token, style: Style) -> CodeTheme {
+        match token {
+            Token::Plain => {}
+            Token::Key => self.key = style,
+            Token::String => self.string = style,
+            Token::Number => self.number = style,
+            Token::Keyword => self.keyword = style,
+            Token::Punctuation => self.punctuation = style,
+            Token::Comment => self.comment = style,
+        }
+        self
+    }
+
+    pub fn get(&self, token: Token) -> Style {
+        match token {
+            Token::Plain => Style::new(),
+            Token::Key => self.key,
+            Token::String => self.string,
+            Token::Number => self.number,
+            Token::Keyword => self.keyword,
+            Token::Punctuation => self.punctuation,
+            Token::Comment => self.comment,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Lang {
+    Json,
+    Yaml,
+    Toml,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// A small built-in highlighter for `json`, `yaml` (or `yml`) and `toml`.
+///
+/// Each line is lexed on its own, so strings spanning multiple lines are not recognized. Other
+/// languages are left plain.
+pub struct Lexer {
+    theme: CodeTheme,
+}
+
+impl Lexer {
+    pub fn new(theme: CodeTheme) -> Lexer {
+        Lexer { theme }
+    }
+
+    /// Split a line of the language into tokens. Unknown languages are a single plain token.
+    pub fn tokens<'a>(&self, language: &str, line: &'a str) -> Vec<(Token, &'a str)> {
+        match language.to_lowercase().as_str() {
+            "json" => lex_line(Lang::Json, line),
+            "yaml" | "yml" => lex_line(Lang::Yaml, line),
+            "toml" => lex_line(Lang::Toml, line),
+            _ => vec![(Token::Plain, line)],
+        }
+    }
+}
+
+impl Highlighter for Lexer {
+    fn highlight(&self, language: &str, source: &str) -> Vec<Text> {
+        let mut texts = Vec::new();
+        for (i, line) in source.split('\n').enumerate() {
+            if i > 0 {
+                texts.push(Text::new("\n".into()));
+            }
+            for (token, s) in self.tokens(language, line) {
+                if !s.is_empty() {
+                    texts.push(Text::new(s.into()).with_style(&self.theme.get(token)));
+                }
+            }
+        }
+        texts
+    }
+}
+
+/// The index after the character at `i`.
+fn next_char(line: &str, i: usize) -> usize {
+    i + line[i..].chars().next().map(char::len_utf8).unwrap_or(1)
+}
+
+fn is_space(line: &str, i: usize) -> bool {
+    line[i..].chars().next().map(char::is_whitespace).unwrap_or(false)
+}
+
+/// Whether the (ascii) character at `i` is followed by whitespace or the end of the line.
+fn before_space(line: &str, i: usize) -> bool {
+    i + 1 == line.len() || is_space(line, i + 1)
+}
+
+fn is_punct(lang: Lang, line: &str, i: usize) -> bool {
+    let b = line.as_bytes();
+    match lang {
+        Lang::Json => b"{}[],:".contains(&b[i]),
+        Lang::Yaml => {
+            b"{}[],".contains(&b[i]) || (b[i] == b':' && before_space(line, i))
+                || (b[i] == b'-' && before_space(line, i)
+                    && b[..i].iter().all(|&c| c == b' ' || c == b'-'))
+        }
+        Lang::Toml => b"{}[],=".contains(&b[i]),
+    }
+}
+
+fn is_comment(lang: Lang, line: &str, i: usize) -> bool {
+    let after_space = line[..i]
+        .chars()
+        .next_back()
+        .map(char::is_whitespace)
+        .unwrap_or(true);
+    lang != Lang::Json && line.as_bytes()[i] == b'#' && after_space
+}
+
+/// Whether the token ending at `i` is a key, i.e. is followed by `:` or `=`.
+fn is_key(lang: Lang, line: &str, mut i: usize) -> bool {
+    while i < line.len() && is_space(line, i) {
+        i = next_char(line, i);
+    }
+    let b = line.as_bytes();
+    i < b.len() && match lang {
+        Lang::Json => b[i] == b':',
+        Lang::Yaml => b[i] == b':' && before_space(line, i),
+        Lang::Toml => b[i] == b'=',
+    }
+}
+
+fn is_keyword(lang: Lang, word: &str) -> bool {
+    match lang {
+        Lang::Json => ["true", "false", "null"].contains(&word),
+        Lang::Yaml => {
+            let word = word.to_lowercase();
+            ["true", "false", "null", "~", "yes", "no"].contains(&word.as_str())
+        }
+        Lang::Toml => ["true", "false", "inf", "nan"].contains(&word),
+    }
+}
+
+fn is_number(word: &str) -> bool {
+    let digits = word.trim_start_matches(['-', '+']);
+    if !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return false;
+    }
+    let digits = digits.replace('_', "");
+    digits.parse::<f64>().is_ok()
+        || ["0x", "0o", "0b"]
+            .iter()
+            .any(|p| digits.starts_with(p) && digits[2..].chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn lex_line(lang: Lang, line: &str) -> Vec<(Token, &str)> {
+    let b = line.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    // toml table headers, i.e. `[package]`
+    let trimmed = line.trim();
+    if lang == Lang::Toml && trimmed.starts_with('[') && trimmed.ends_with(']') {
+        let start = line.len() - line.trim_start().len();
+        out.push((Token::Plain, &line[..start]));
+        out.push((Token::Key, trimmed));
+        out.push((Token::Plain, &line[start + trimmed.len()..]));
+        return out;
+    }
+
+    while i < b.len() {
+        let start = i;
+        let c = b[i];
+        let token = if is_space(line, i) {
+            while i < b.len() && is_space(line, i) {
+                i = next_char(line, i);
+            }
+            Token::Plain
+        } else if is_comment(lang, line, i) {
+            i = b.len();
+            Token::Comment
+        } else if c == b'"' || (c == b'\'' && lang != Lang::Json) {
+            i += 1;
+            while i < b.len() {
+                let escaped = b[i] == b'\\' && c == b'"';
+                let end = b[i] == c;
+                i = next_char(line, i);
+                if escaped && i < b.len() {
+                    i = next_char(line, i);
+                } else if end {
+                    break;
+                }
+            }
+            if is_key(lang, line, i) {
+                Token::Key
+            } else {
+                Token::String
+            }
+        } else if is_punct(lang, line, i) {
+            i += 1;
+            Token::Punctuation
+        } else {
+            // a word, which includes spaces in yaml (i.e. `key with spaces: value`)
+            i = next_char(line, i);
+            while i < b.len() && !is_punct(lang, line, i) && !is_comment(lang, line, i)
+                && (lang == Lang::Yaml || !is_space(line, i))
+            {
+                i = next_char(line, i);
+            }
+            // the word starts with a non-space, so this always advances
+            i = start + line[start..i].trim_end().len();
+            let word = &line[start..i];
+            if is_key(lang, line, i) {
+                Token::Key
+            } else if is_keyword(lang, word) {
+                Token::Keyword
+            } else if is_number(word) {
+                Token::Number
+            } else if lang == Lang::Yaml {
+                Token::String
+            } else {
+                Token::Plain
+            }
+        };
+        out.push((token, &line[start..i]));
+    }
+    out
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// A block of source code, highlighted with the built-in [`Lexer`](struct.Lexer.html) or any
+/// other [`Highlighter`](trait.Highlighter.html).
+///
+/// The code can have line numbers and a gutter (`│`) before it. Marked lines are shown with `>`
+/// in the gutter and in the mark style. Tabs are replaced with four spaces, `\r\n` line endings
+/// with `\n`, and lines which don't fit in the width are truncated.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let source = "\
+/// [package]
+/// name = \"termstyle\"
+/// version = \"0.1.2\"
+/// ";
+/// let mut code = Code::new("toml", source)
+///     .line_numbers(true)
+///     .mark_lines(3, 3);
+/// code.set_plain();
+///
+/// let expected = "\
+/// 1 │ [package]
+/// 2 │ name = \"termstyle\"
+/// 3 > version = \"0.1.2\"
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint(&mut result, &[El::Code(code)]).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Code {
+    language: String,
+    source: String,
+    texts: Vec<Text>,
+    line_numbers: bool,
+    first_line: usize,
+    gutter: bool,
+    marks: Vec<(usize, usize)>,
+    gutter_style: Style,
+    mark_style: Style,
+}
+
+impl Code {
+    /// Create a code block of the language, highlighted with the default `Lexer`.
+    pub fn new<L: Into<String>, S: Into<String>>(language: L, source: S) -> Code {
+        let language = language.into();
+        let source = source.into().replace("\r\n", "\n").replace('\t', "    ");
+        let texts = Lexer::default().highlight(&language, &source);
+        Code {
+            language,
+            source,
+            texts,
+            line_numbers: false,
+            first_line: 1,
+            gutter: true,
+            marks: Vec::new(),
+            gutter_style: Style::new().dim(),
+            mark_style: Style::new().bold(),
+        }
+    }
+
+    /// Highlight the source with the `Lexer` using the theme.
+    pub fn theme(self, theme: CodeTheme) -> Code {
+        self.highlighter(&Lexer::new(theme))
+    }
+
+    /// Highlight the source with another highlighter.
+    pub fn highlighter<H: Highlighter>(mut self, highlighter: &H) -> Code {
+        self.texts = highlighter.highlight(&self.language, &self.source);
+        self
+    }
+
+    /// Show the line numbers before the code.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Code {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Set the number of the first line (default 1), i.e. when showing part of a file.
+    pub fn first_line(mut self, first_line: usize) -> Code {
+        self.first_line = first_line;
+        self
+    }
+
+    /// Whether to draw the gutter between the line numbers and the code (default true).
+    pub fn gutter(mut self, gutter: bool) -> Code {
+        self.gutter = gutter;
+        self
+    }
+
+    /// Mark the lines from `first` to `last` (inclusive), numbered from `first_line`.
+    pub fn mark_lines(mut self, first: usize, last: usize) -> Code {
+        self.marks.push((first, last));
+        self
+    }
+
+    /// Set the style of the line numbers and gutter (dim by default).
+    pub fn gutter_style(mut self, style: Style) -> Code {
+        self.gutter_style = style;
+        self
+    }
+
+    /// Set the style applied on top of marked lines (bold by default).
+    pub fn mark_style(mut self, style: Style) -> Code {
+        self.mark_style = style;
+        self
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Clears the highlighting and other formatting.
+    pub fn set_plain(&mut self) {
+        for t in &mut self.texts {
+            t.set_plain();
+        }
+        self.gutter_style = Style::new();
+        self.mark_style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let width = width.unwrap_or(DEFAULT_WIDTH);
+        let lines = inline_lines(&self.texts);
+        let last = self.first_line + lines.len().saturating_sub(1);
+        let number_width = last.to_string().len();
+
+        let mut out = Vec::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            let number = self.first_line + i;
+            let marked = self.marks
+                .iter()
+                .any(|&(first, last)| first <= number && number <= last);
+            let mut prefix = String::new();
+            if self.line_numbers {
+                prefix.push_str(&format!("{:>1$} ", number, number_width));
+            }
+            if self.gutter {
+                prefix.push_str(if marked { "> " } else { "│ " });
+            }
+            let style = if marked {
+                self.mark_style
+            } else {
+                self.gutter_style
+            };
+            let mut l = Vec::new();
+            if !prefix.is_empty() {
+                l.push(Text::new(prefix.clone()).with_style(&style));
+            }
+            let code = layout::truncate_end(&line, width.saturating_sub(layout::str_width(&prefix)));
+            l.extend(code.into_iter().map(|t| {
+                if marked {
+                    t.with_style(&self.mark_style)
+                } else {
+                    t
+                }
+            }));
+            out.push(l);
+        }
+        out
+    }
+}
@@ -0,0 +1,195 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Laying out blocks side by side.
+
+use std_prelude::*;
+
+use block::{block_lines_with, DEFAULT_WIDTH};
+use layout::{self, Line};
+use El;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// The width of a block in [`Columns`](struct.Columns.html).
+pub enum Width {
+    /// Exactly this many columns wide.
+    Fixed(usize),
+    /// A share of the width left after the fixed blocks, proportional to the other ratios.
+    Ratio(usize),
+}
+
+impl Default for Width {
+    fn default() -> Width {
+        Width::Ratio(1)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Blocks of elements laid out next to each other.
+///
+/// Each block is laid out in its own width (its text is wrapped to fit) and the lines of the
+/// blocks are zipped together, padding the shorter blocks. Lines which are still too wide (i.e. a
+/// table which can't shrink enough) are truncated, so the columns always stay aligned. Each
+/// `Text` is painted on its own, so styles never bleed into the next column.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let columns = Columns::new()
+///     .column(Width::Fixed(8), vec![El::plain("old\nvalue".into())])
+///     .column(Width::Ratio(1), vec![El::plain("the new value, which wraps".into())]);
+///
+/// let expected = "\
+/// old       the new value,
+/// value     which wraps
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::Columns(columns)], 24).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Columns {
+    blocks: Vec<(Width, Vec<El>)>,
+    gap: usize,
+}
+
+impl Default for Columns {
+    fn default() -> Columns {
+        Columns {
+            blocks: Vec::new(),
+            gap: 2,
+        }
+    }
+}
+
+impl Columns {
+    /// Create an empty layout, add the blocks with `column`.
+    pub fn new() -> Columns {
+        Columns::default()
+    }
+
+    /// Add a block to the right of the others.
+    pub fn column(mut self, width: Width, content: Vec<El>) -> Columns {
+        self.blocks.push((width, content));
+        self
+    }
+
+    /// Set the number of spaces between the blocks (default 2).
+    pub fn gap(mut self, gap: usize) -> Columns {
+        self.gap = gap;
+        self
+    }
+
+    pub fn get_blocks(&self) -> &[(Width, Vec<El>)] {
+        &self.blocks
+    }
+
+    pub fn get_gap(&self) -> usize {
+        self.gap
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        for el in self.blocks.iter_mut().flat_map(|b| b.1.iter_mut()) {
+            el.set_plain();
+        }
+    }
+
+    /// The width of each block when laid out in `width`.
+    fn widths(&self, width: usize) -> Vec<usize> {
+        let gaps = self.gap * self.blocks.len().saturating_sub(1);
+        let fixed: usize = self.blocks
+            .iter()
+            .map(|b| match b.0 {
+                Width::Fixed(w) => w,
+                Width::Ratio(_) => 0,
+            })
+            .sum();
+        let left = width.saturating_sub(gaps + fixed);
+        let total: usize = self.blocks
+            .iter()
+            .map(|b| match b.0 {
+                Width::Ratio(r) => r,
+                Width::Fixed(_) => 0,
+            })
+            .sum();
+
+        let mut widths: Vec<usize> = self.blocks
+            .iter()
+            .map(|b| match b.0 {
+                Width::Fixed(w) => w,
+                Width::Ratio(r) if total > 0 => left * r / total,
+                Width::Ratio(_) => 0,
+            })
+            .collect();
+        // give the columns lost to rounding to the first proportional blocks
+        let assigned: usize = widths
+            .iter()
+            .zip(&self.blocks)
+            .filter(|&(_, b)| match b.0 {
+                Width::Ratio(_) => true,
+                Width::Fixed(_) => false,
+            })
+            .map(|(w, _)| *w)
+            .sum();
+        let mut rest = if total > 0 { left - assigned } else { 0 };
+        for (w, b) in widths.iter_mut().zip(&self.blocks) {
+            match b.0 {
+                Width::Ratio(r) if r > 0 && rest > 0 => {
+                    *w += 1;
+                    rest -= 1;
+                }
+                _ => {}
+            }
+        }
+        widths
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let widths = self.widths(width.unwrap_or(DEFAULT_WIDTH));
+        let blocks: Vec<Vec<Line>> = self.blocks
+            .iter()
+            .zip(&widths)
+            .map(|(b, &w)| block_lines_with(&b.1, Some(w), |el| el.lines(Some(w))))
+            .collect();
+        let height = blocks.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut out = Vec::new();
+        for row in 0..height {
+            // don't pad the blocks after the last one with any text
+            let last = blocks
+                .iter()
+                .rposition(|b| b.get(row).map(|l| !l.is_empty()).unwrap_or(false));
+            let last = match last {
+                Some(last) => last,
+                None => {
+                    out.push(Vec::new());
+                    continue;
+                }
+            };
+            let mut line: Line = Vec::new();
+            let mut end = 0;
+            for (i, (block, &w)) in blocks.iter().zip(&widths).enumerate().take(last + 1) {
+                layout::pad_line(&mut line, end);
+                if let Some(l) = block.get(row) {
+                    line.extend(layout::truncate_end(l, w));
+                }
+                end += w + self.gap;
+                if i < last {
+                    layout::pad_line(&mut line, end);
+                }
+            }
+            out.push(line);
+        }
+        out
+    }
+}
@@ -0,0 +1,287 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Converting tables from and to other formats.
+
+use std::io;
+use std_prelude::*;
+
+use csv;
+use serde::Serialize;
+use serde_json;
+
+use table::PlainGrid;
+use {Table, Text};
+
+impl Table {
+    /// Create a table from csv data, using the first record as the header.
+    ///
+    /// Records may have different lengths. Use
+    /// [`format_column`](struct.Table.html#method.format_column) to style the cells.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let data = "\
+    /// name,size
+    /// foo.rs,12
+    /// bar.rs,7
+    /// ";
+    /// let table = Table::from_csv(data.as_bytes())
+    ///     .unwrap()
+    ///     .format_column("size", |s| vec![Text::new(s.into()).bold()]);
+    ///
+    /// assert_eq!(table.rows().len(), 2);
+    /// assert!(table.rows()[0][1][0].is_bold());
+    /// # }
+    /// ```
+    pub fn from_csv<R: io::Read>(reader: R) -> io::Result<Table> {
+        from_delimited(reader, b',')
+    }
+
+    /// Create a table from tab separated data, using the first record as the header.
+    ///
+    /// See [`from_csv`](#method.from_csv).
+    pub fn from_tsv<R: io::Read>(reader: R) -> io::Result<Table> {
+        from_delimited(reader, b'\t')
+    }
+
+    /// Create a table from serializable records (i.e. structs), using the field names as the
+    /// header.
+    ///
+    /// Each record must serialize to a flat sequence of values, as is required by csv. Use
+    /// [`format_column`](struct.Table.html#method.format_column) to style the cells.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// #[macro_use]
+    /// extern crate serde_derive;
+    /// use termstyle::*;
+    ///
+    /// #[derive(Serialize)]
+    /// struct File {
+    ///     name: String,
+    ///     size: u64,
+    /// }
+    ///
+    /// # fn main() {
+    /// let files = vec![
+    ///     File { name: "foo.rs".into(), size: 12 },
+    ///     File { name: "bar.rs".into(), size: 7 },
+    /// ];
+    /// let mut table = Table::from_records(&files).unwrap();
+    /// table.set_plain();
+    ///
+    /// let expected = "\
+    /// name   size
+    /// ───────────
+    /// foo.rs 12
+    /// bar.rs 7
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// table.paint(&mut result).unwrap();
+    /// assert_eq!(expected, String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn from_records<T: Serialize>(records: &[T]) -> io::Result<Table> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for record in records {
+            writer.serialize(record)?;
+        }
+        let data = writer.into_inner().map_err(|e| e.into_error())?;
+        Table::from_csv(data.as_slice())
+    }
+
+    /// Write the table as csv, without any styling.
+    ///
+    /// Only the last header row (the column names) is written, followed by the body and the
+    /// footer rows. Group headers above the column names are left out. The text of a cell
+    /// spanning multiple columns or rows is written to its top left field.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let table = Table::new(vec![
+    ///     vec![vec![Text::new("foo, ".into()), Text::new("bar".into()).bold()]],
+    /// ]).header(vec![vec![Text::new("name".into())]]);
+    ///
+    /// let mut result = Vec::new();
+    /// table.write_csv(&mut result).unwrap();
+    /// assert_eq!("name\n\"foo, bar\"\n", String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn write_csv<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.write_delimited(w, b',')
+    }
+
+    /// Write the table as tab separated values, without any styling.
+    ///
+    /// See [`write_csv`](#method.write_csv).
+    pub fn write_tsv<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.write_delimited(w, b'\t')
+    }
+
+    /// Write the table as json, without any styling.
+    ///
+    /// The table is written as an object with the body in `rows` and the footer rows in `footer`
+    /// (empty if the table has no footer). If the table has a header the rows are written as
+    /// objects keyed by the column names (in column order), otherwise as arrays of strings. See
+    /// [`write_csv`](#method.write_csv) for which cells are written.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let table = Table::new(vec![vec![cell("foo"), cell("12")]])
+    ///     .header(vec![cell("name"), cell("size")]);
+    ///
+    /// let mut result = Vec::new();
+    /// table.write_json(&mut result).unwrap();
+    /// assert_eq!(
+    ///     "{\"rows\":[{\"name\":\"foo\",\"size\":\"12\"}],\"footer\":[]}\n",
+    ///     String::from_utf8(result).unwrap()
+    /// );
+    /// # }
+    /// ```
+    pub fn write_json<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        let PlainGrid { names, rows, footer } = self.plain_grid();
+        w.write_all(b"{\"rows\":")?;
+        write_json_rows(&mut w, &names, &rows)?;
+        w.write_all(b",\"footer\":")?;
+        write_json_rows(&mut w, &names, &footer)?;
+        w.write_all(b"}\n")
+    }
+
+    /// Write the table as a markdown (pipe) table, without any styling.
+    ///
+    /// Markdown tables require a header, so a table without one gets an empty header row. `|` is
+    /// escaped and newlines are replaced with `<br>`. See [`write_csv`](#method.write_csv) for
+    /// which cells are written.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let table = Table::new(vec![vec![cell("a|b"), cell("12")]])
+    ///     .header(vec![cell("name"), cell("size")]);
+    ///
+    /// let expected = "\
+    /// | name | size |
+    /// | --- | --- |
+    /// | a\\|b | 12 |
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// table.write_markdown(&mut result).unwrap();
+    /// assert_eq!(expected, String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn write_markdown<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        let PlainGrid { names, rows, footer } = self.plain_grid();
+        let ncols = names
+            .as_ref()
+            .map(Vec::len)
+            .or_else(|| rows.first().map(Vec::len))
+            .unwrap_or(0);
+        let names = names.unwrap_or_else(|| vec![String::new(); ncols]);
+        write_markdown_row(&mut w, &names)?;
+        write_markdown_row(&mut w, &vec!["---".to_string(); ncols])?;
+        for row in rows.iter().chain(&footer) {
+            write_markdown_row(&mut w, row)?;
+        }
+        Ok(())
+    }
+
+    fn write_delimited<W: io::Write>(&self, w: W, delimiter: u8) -> io::Result<()> {
+        let PlainGrid { names, rows, footer } = self.plain_grid();
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_writer(w);
+        for row in names.iter().chain(&rows).chain(&footer) {
+            writer.write_record(row)?;
+        }
+        writer.flush()
+    }
+}
+
+fn write_json_rows<W: io::Write>(
+    w: &mut W,
+    names: &Option<Vec<String>>,
+    rows: &[Vec<String>],
+) -> io::Result<()> {
+    w.write_all(b"[")?;
+    for (r, row) in rows.iter().enumerate() {
+        if r > 0 {
+            w.write_all(b",")?;
+        }
+        match *names {
+            Some(ref names) => {
+                w.write_all(b"{")?;
+                for (c, (name, value)) in names.iter().zip(row).enumerate() {
+                    if c > 0 {
+                        w.write_all(b",")?;
+                    }
+                    serde_json::to_writer(&mut *w, name)?;
+                    w.write_all(b":")?;
+                    serde_json::to_writer(&mut *w, value)?;
+                }
+                w.write_all(b"}")?;
+            }
+            None => serde_json::to_writer(&mut *w, row)?,
+        }
+    }
+    w.write_all(b"]")
+}
+
+fn write_markdown_row<W: io::Write>(w: &mut W, row: &[String]) -> io::Result<()> {
+    w.write_all(b"|")?;
+    for cell in row {
+        let cell = cell.replace('|', "\\|").replace('\n', "<br>");
+        write!(w, " {} |", cell)?;
+    }
+    w.write_all(b"\n")
+}
+
+fn from_delimited<R: io::Read>(reader: R, delimiter: u8) -> io::Result<Table> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .has_headers(false)
+        .from_reader(reader);
+
+    let mut records = reader.records();
+    let header = match records.next() {
+        Some(header) => to_row(&header?),
+        None => return Ok(Table::new(Vec::new())),
+    };
+    let mut rows = Vec::new();
+    for record in records {
+        rows.push(to_row(&record?));
+    }
+    Ok(Table::new(rows).header(header))
+}
+
+fn to_row(record: &csv::StringRecord) -> Vec<Vec<Text>> {
+    record
+        .iter()
+        .map(|field| vec![Text::new(field.into())])
+        .collect()
+}
@@ -0,0 +1,312 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Compiler style diagnostics, with source snippets and labeled spans.
+
+use std_prelude::*;
+
+use layout::{self, repeat, Line};
+use {Color, Style, Text};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// How severe a [`Diagnostic`](struct.Diagnostic.html) is, which decides its header and color.
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Severity {
+    fn name(&self) -> &'static str {
+        match *self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match *self {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+            Severity::Note => Color::Cyan,
+            Severity::Help => Color::Green,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// A span of a source line, underlined in a [`Diagnostic`](struct.Diagnostic.html).
+///
+/// Primary labels are underlined with `^` in the color of the severity, secondary labels with `-`.
+pub struct Label {
+    line: usize,
+    col: usize,
+    len: usize,
+    text: Vec<Text>,
+    primary: bool,
+}
+
+impl Label {
+    /// Label `len` characters from `col` of the line, both numbered from 1.
+    pub fn primary(line: usize, col: usize, len: usize, text: Vec<Text>) -> Label {
+        Label {
+            line,
+            col,
+            len,
+            text,
+            primary: true,
+        }
+    }
+
+    /// Label `len` characters like `primary`, i.e. to show related code.
+    pub fn secondary(line: usize, col: usize, len: usize, text: Vec<Text>) -> Label {
+        Label {
+            primary: false,
+            ..Label::primary(line, col, len, text)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// A diagnostic like the ones of `rustc`: a header, the location, the labeled source lines and
+/// notes.
+///
+/// Only the source lines with labels are shown, gaps between them are shown as `...`. Columns
+/// count characters (a tab is one column), tabs are shown as four spaces.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let t = |s: &str| vec![Text::new(s.into())];
+/// let mut diag = Diagnostic::error(t("unknown key `colr`"))
+///     .code("E001")
+///     .path("config.yaml")
+///     .source("text: hello\ncolr: red\n", 1)
+///     .label(Label::primary(2, 1, 4, t("did you mean `color`?")))
+///     .note(t("keys are case sensitive"));
+/// diag.set_plain();
+///
+/// let expected = "\
+/// error[E001]: unknown key `colr`
+/// \x20--> config.yaml:2:1
+/// \x20 |
+/// 2 | colr: red
+/// \x20 | ^^^^ did you mean `color`?
+/// \x20 |
+/// \x20 = note: keys are case sensitive
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint(&mut result, &[El::Diagnostic(diag)]).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Diagnostic {
+    severity: Severity,
+    code: Option<String>,
+    message: Vec<Text>,
+    path: Option<String>,
+    source: Vec<String>,
+    first_line: usize,
+    labels: Vec<Label>,
+    notes: Vec<(Severity, Vec<Text>)>,
+    plain: bool,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: Vec<Text>) -> Diagnostic {
+        Diagnostic {
+            severity,
+            message,
+            first_line: 1,
+            ..Diagnostic::default()
+        }
+    }
+
+    pub fn error(message: Vec<Text>) -> Diagnostic {
+        Diagnostic::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: Vec<Text>) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, message)
+    }
+
+    /// Set the code shown after the severity, i.e. `error[E001]`.
+    pub fn code<S: Into<String>>(mut self, code: S) -> Diagnostic {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Set the path of the file, shown with the line and column of the first primary label.
+    pub fn path<S: Into<String>>(mut self, path: S) -> Diagnostic {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Set the source the labels refer to, with the number of its first line.
+    ///
+    /// The source can be a part of a file, i.e. the lines around the labels.
+    pub fn source(mut self, source: &str, first_line: usize) -> Diagnostic {
+        self.source = source.lines().map(String::from).collect();
+        self.first_line = first_line;
+        self
+    }
+
+    /// Add a label below the others.
+    pub fn label(mut self, label: Label) -> Diagnostic {
+        self.labels.push(label);
+        self
+    }
+
+    /// Add a `= note:` line after the snippet.
+    pub fn note(mut self, note: Vec<Text>) -> Diagnostic {
+        self.notes.push((Severity::Note, note));
+        self
+    }
+
+    /// Add a `= help:` line after the snippet.
+    pub fn help(mut self, help: Vec<Text>) -> Diagnostic {
+        self.notes.push((Severity::Help, help));
+        self
+    }
+
+    pub fn get_severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Clears the formatting of the texts and of the diagnostic itself.
+    pub fn set_plain(&mut self) {
+        let texts = self.message
+            .iter_mut()
+            .chain(self.labels.iter_mut().flat_map(|l| l.text.iter_mut()))
+            .chain(self.notes.iter_mut().flat_map(|n| n.1.iter_mut()));
+        for t in texts {
+            t.set_plain();
+        }
+        self.plain = true;
+    }
+
+    fn style(&self, style: Style) -> Style {
+        if self.plain {
+            Style::new()
+        } else {
+            style
+        }
+    }
+
+    pub(crate) fn lines(&self) -> Vec<Line> {
+        let severity = self.style(Style::new().bold().color(self.severity.color()));
+        let gutter = self.style(Style::new().bold().color(Color::Blue));
+        let bold = self.style(Style::new().bold());
+
+        let mut out = Vec::new();
+        let mut header = self.severity.name().to_string();
+        if let Some(ref code) = self.code {
+            header.push_str(&format!("[{}]", code));
+        }
+        let mut line = vec![Text::new(header).with_style(&severity)];
+        line.push(Text::new(": ".into()).with_style(&bold));
+        line.extend(self.message.iter().map(|t| t.clone().with_style(&bold)));
+        out.push(line);
+
+        // the labeled lines which are in the source, in order
+        let mut shown = self.labels
+            .iter()
+            .map(|l| l.line)
+            .filter(|&n| n >= self.first_line && n - self.first_line < self.source.len())
+            .collect::<Vec<_>>();
+        shown.sort();
+        shown.dedup();
+        let number_width = shown.last().map(|n| n.to_string().len()).unwrap_or(0);
+        let pad = repeat(' ', number_width);
+        let empty_gutter = || vec![Text::new(format!("{} |", pad)).with_style(&gutter)];
+
+        if let Some(ref path) = self.path {
+            let first = self.labels
+                .iter()
+                .find(|l| l.primary)
+                .or_else(|| self.labels.first());
+            let location = match first {
+                Some(l) => format!("{}:{}:{}", path, l.line, l.col),
+                None => path.clone(),
+            };
+            out.push(vec![
+                Text::new(format!("{}--> ", pad)).with_style(&gutter),
+                Text::new(location),
+            ]);
+        }
+
+        if !shown.is_empty() {
+            out.push(empty_gutter());
+        }
+        for (i, &number) in shown.iter().enumerate() {
+            if i > 0 && shown[i - 1] + 1 < number {
+                out.push(vec![Text::new("...".into()).with_style(&gutter)]);
+            }
+            let source = &self.source[number - self.first_line];
+            let expand = |s: &str| s.replace('\t', "    ");
+            let mut line = vec![
+                Text::new(format!("{:>1$} |", number, number_width)).with_style(&gutter),
+            ];
+            if !source.is_empty() {
+                line.push(Text::new(format!(" {}", expand(source))));
+            }
+            out.push(line);
+
+            let mut labels = self.labels
+                .iter()
+                .filter(|l| l.line == number)
+                .collect::<Vec<_>>();
+            labels.sort_by_key(|l| l.col);
+            for label in labels {
+                let before = source.chars().take(label.col.saturating_sub(1)).collect::<String>();
+                let span = source
+                    .chars()
+                    .skip(label.col.saturating_sub(1))
+                    .take(label.len)
+                    .collect::<String>();
+                let (c, style) = if label.primary {
+                    ('^', severity)
+                } else {
+                    ('-', gutter)
+                };
+                let mut line = empty_gutter();
+                let indent = layout::str_width(&expand(&before));
+                line.push(Text::new(format!(" {}", repeat(' ', indent))));
+                let marks = repeat(c, layout::str_width(&expand(&span)).max(1));
+                line.push(Text::new(marks).with_style(&style));
+                if !label.text.is_empty() {
+                    line.push(Text::new(" ".into()));
+                    line.extend(label.text.iter().map(|t| t.clone().with_style(&style)));
+                }
+                out.push(line);
+            }
+        }
+
+        if !self.notes.is_empty() && !shown.is_empty() {
+            out.push(empty_gutter());
+        }
+        for &(kind, ref note) in &self.notes {
+            let mut line = vec![
+                Text::new(format!("{} = ", pad)).with_style(&gutter),
+                Text::new(kind.name().into()).with_style(&bold),
+                Text::new(": ".into()),
+            ];
+            line.extend(note.iter().cloned());
+            out.push(line);
+        }
+        out
+    }
+}
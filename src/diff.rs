@@ -0,0 +1,430 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Line diffs of two texts, unified or side by side.
+
+use std::cmp;
+use std_prelude::*;
+
+use block::DEFAULT_WIDTH;
+use layout::{self, Line};
+use {Color, Style, Text};
+
+/// Shown after the last line of a text which doesn't end with a newline.
+const NO_NEWLINE: &str = "\\ No newline at end of file";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// An edit turning `old` into `new`, with the indexes of the items.
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// The shortest edits turning `old` into `new`, from their longest common subsequence.
+///
+/// The subsequence is found with Hirschberg's algorithm, which takes `O(n * m)` time but only
+/// linear memory.
+fn diff_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|&(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|&(a, b)| a == b)
+        .count();
+
+    let mut ops = (0..prefix).map(|i| Op::Equal(i, i)).collect::<Vec<_>>();
+    lcs_ops(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+        (prefix, prefix),
+        &mut ops,
+    );
+    ops.extend((0..suffix).map(|k| Op::Equal(old.len() - suffix + k, new.len() - suffix + k)));
+    ops
+}
+
+/// Push the edits turning `a` into `b`, which start at the indexes `at` of the full texts.
+///
+/// Deletions come before insertions where both are possible.
+fn lcs_ops<T: PartialEq>(a: &[T], b: &[T], at: (usize, usize), ops: &mut Vec<Op>) {
+    if a.is_empty() || b.is_empty() {
+        ops.extend((0..a.len()).map(|i| Op::Delete(at.0 + i)));
+        ops.extend((0..b.len()).map(|j| Op::Insert(at.1 + j)));
+        return;
+    }
+    if a.len() == 1 {
+        match b.iter().position(|x| *x == a[0]) {
+            Some(j) => {
+                ops.extend((0..j).map(|k| Op::Insert(at.1 + k)));
+                ops.push(Op::Equal(at.0, at.1 + j));
+                ops.extend((j + 1..b.len()).map(|k| Op::Insert(at.1 + k)));
+            }
+            None => {
+                ops.push(Op::Delete(at.0));
+                ops.extend((0..b.len()).map(|k| Op::Insert(at.1 + k)));
+            }
+        }
+        return;
+    }
+
+    // split `b` where the halves of `a` have the longest common subsequences with its parts
+    let mid = a.len() / 2;
+    let forward = lcs_lengths(a[..mid].iter(), b.iter());
+    let mut backward = lcs_lengths(a[mid..].iter().rev(), b.iter().rev());
+    backward.reverse();
+    let split = (0..=b.len())
+        .max_by_key(|&j| (forward[j] + backward[j], cmp::Reverse(j)))
+        .expect("the range is not empty");
+    lcs_ops(&a[..mid], &b[..split], at, ops);
+    lcs_ops(&a[mid..], &b[split..], (at.0 + mid, at.1 + split), ops);
+}
+
+/// The length of the longest common subsequence of `a` and each prefix of `b`.
+fn lcs_lengths<'a, T, A, B>(a: A, b: B) -> Vec<usize>
+where
+    T: PartialEq + 'a,
+    A: Iterator<Item = &'a T>,
+    B: Iterator<Item = &'a T> + Clone,
+{
+    let mut row = vec![0; b.clone().count() + 1];
+    for x in a {
+        let mut diagonal = 0;
+        for (j, y) in b.clone().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if x == y {
+                diagonal + 1
+            } else {
+                cmp::max(above, row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// The ranges of `ops` to show, each change with `context` equal lines around it.
+fn hunks(ops: &[Op], context: usize) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (k, op) in ops.iter().enumerate() {
+        if let Op::Equal(..) = *op {
+            continue;
+        }
+        let start = k.saturating_sub(context);
+        let end = cmp::min(ops.len(), k.saturating_add(context).saturating_add(1));
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
+
+/// The `@@ -1,2 +1,3 @@` header of the hunk.
+fn hunk_header(ops: &[Op], hunk: (usize, usize)) -> String {
+    let is_old = |op: &Op| !matches!(*op, Op::Insert(_));
+    let is_new = |op: &Op| !matches!(*op, Op::Delete(_));
+    let range = |before: usize, count: usize| {
+        if count == 0 {
+            format!("{},0", before)
+        } else {
+            format!("{},{}", before + 1, count)
+        }
+    };
+    let old_before = ops[..hunk.0].iter().filter(|o| is_old(o)).count();
+    let new_before = ops[..hunk.0].iter().filter(|o| is_new(o)).count();
+    let old_count = ops[hunk.0..hunk.1].iter().filter(|o| is_old(o)).count();
+    let new_count = ops[hunk.0..hunk.1].iter().filter(|o| is_new(o)).count();
+    format!(
+        "@@ -{} +{} @@",
+        range(old_before, old_count),
+        range(new_before, new_count)
+    )
+}
+
+/// Split a line into words, runs of whitespace and single punctuation characters.
+fn words(line: &str) -> Vec<&str> {
+    let kind = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut last = None;
+    for (i, c) in line.char_indices() {
+        let k = kind(c);
+        if i > 0 && (last != Some(k) || k == 2) {
+            words.push(&line[start..i]);
+            start = i;
+        }
+        last = Some(k);
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// The differences between two texts, line by line.
+///
+/// The unified diff shows the changed lines prefixed with `-` and `+` in hunks with a few lines
+/// of context, a text which doesn't end with a newline is marked with `\ No newline at end of
+/// file`. Side by side, the old lines are on the left and the new lines on the right with
+/// `<`, `>` or `|` between them, and the changed words of changed lines are highlighted.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let old = "a\nb\nc\n";
+/// let new = "a\nB\nc\nd\n";
+/// let mut diff = Diff::new(old, new).names("old", "new");
+/// diff.set_plain();
+///
+/// let expected = "\
+/// --- old
+/// +++ new
+/// @@ -1,3 +1,4 @@
+/// \x20a
+/// -b
+/// +B
+/// \x20c
+/// +d
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint(&mut result, &[El::Diff(diff)]).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Diff {
+    old: Vec<String>,
+    new: Vec<String>,
+    /// Whether the texts don't end with a newline.
+    old_unterminated: bool,
+    new_unterminated: bool,
+    names: Option<(String, String)>,
+    context: usize,
+    side_by_side: bool,
+    plain: bool,
+}
+
+impl Diff {
+    pub fn new<O: AsRef<str>, N: AsRef<str>>(old: O, new: N) -> Diff {
+        let lines = |s: &str| s.lines().map(|l| l.replace('\t', "    ")).collect();
+        let unterminated = |s: &str| !s.is_empty() && !s.ends_with('\n');
+        let (old, new) = (old.as_ref(), new.as_ref());
+        Diff {
+            old: lines(old),
+            new: lines(new),
+            old_unterminated: unterminated(old),
+            new_unterminated: unterminated(new),
+            context: 3,
+            ..Diff::default()
+        }
+    }
+
+    /// Show the names of the texts (i.e. their paths) in a `---`/`+++` header.
+    pub fn names<O: Into<String>, N: Into<String>>(mut self, old: O, new: N) -> Diff {
+        self.names = Some((old.into(), new.into()));
+        self
+    }
+
+    /// Set the number of equal lines shown around each change (default 3). Use `usize::MAX` to
+    /// show the whole text.
+    pub fn context(mut self, context: usize) -> Diff {
+        self.context = context;
+        self
+    }
+
+    /// Show the old and new lines side by side.
+    pub fn side_by_side(mut self, side_by_side: bool) -> Diff {
+        self.side_by_side = side_by_side;
+        self
+    }
+
+    /// Whether the texts are equal.
+    pub fn is_empty(&self) -> bool {
+        self.old == self.new && self.old_unterminated == self.new_unterminated
+    }
+
+    /// Clears the formatting of the diff.
+    pub fn set_plain(&mut self) {
+        self.plain = true;
+    }
+
+    fn style(&self, style: Style) -> Style {
+        if self.plain {
+            Style::new()
+        } else {
+            style
+        }
+    }
+
+    /// The lines to compare: a missing newline makes the last line differ from a terminated one.
+    fn keys(lines: &[String], unterminated: bool) -> Vec<(&str, bool)> {
+        let last = lines.len().saturating_sub(1);
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (l.as_str(), unterminated && i == last))
+            .collect()
+    }
+
+    /// Whether the op shows the last line of a text which doesn't end with a newline.
+    fn is_unterminated(&self, op: &Op) -> bool {
+        let old = |i: usize| self.old_unterminated && i + 1 == self.old.len();
+        let new = |j: usize| self.new_unterminated && j + 1 == self.new.len();
+        match *op {
+            Op::Equal(i, _) | Op::Delete(i) => old(i),
+            Op::Insert(j) => new(j),
+        }
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let ops = diff_ops(
+            &Diff::keys(&self.old, self.old_unterminated),
+            &Diff::keys(&self.new, self.new_unterminated),
+        );
+        let hunks = hunks(&ops, self.context);
+        let bold = self.style(Style::new().bold());
+        let header = self.style(Style::new().color(Color::Cyan));
+        let deleted = self.style(Style::new().color(Color::Red));
+        let inserted = self.style(Style::new().color(Color::Green));
+
+        let mut out = Vec::new();
+        if hunks.is_empty() {
+            return out;
+        }
+        if let Some((ref old, ref new)) = self.names {
+            out.push(vec![Text::new(format!("--- {}", old)).with_style(&bold)]);
+            out.push(vec![Text::new(format!("+++ {}", new)).with_style(&bold)]);
+        }
+        for &hunk in &hunks {
+            out.push(vec![Text::new(hunk_header(&ops, hunk)).with_style(&header)]);
+            let ops = &ops[hunk.0..hunk.1];
+            if self.side_by_side {
+                self.side_by_side_lines(&mut out, ops, width.unwrap_or(DEFAULT_WIDTH));
+                if ops.iter().any(|op| self.is_unterminated(op)) {
+                    out.push(vec![Text::new(NO_NEWLINE.into())]);
+                }
+                continue;
+            }
+            for op in ops {
+                out.push(match *op {
+                    Op::Equal(i, _) => vec![Text::new(format!(" {}", self.old[i]))],
+                    Op::Delete(i) => vec![Text::new(format!("-{}", self.old[i])).with_style(&deleted)],
+                    Op::Insert(j) => vec![Text::new(format!("+{}", self.new[j])).with_style(&inserted)],
+                });
+                if self.is_unterminated(op) {
+                    out.push(vec![Text::new(NO_NEWLINE.into())]);
+                }
+            }
+        }
+        out
+    }
+
+    fn side_by_side_lines(&self, out: &mut Vec<Line>, ops: &[Op], width: usize) {
+        let half = width.saturating_sub(3) / 2;
+        let deleted = self.style(Style::new().color(Color::Red));
+        let inserted = self.style(Style::new().color(Color::Green));
+        let deleted_word = self.style(Style::new().bold().color(Color::White).bg(Color::Red));
+        let inserted_word = self.style(Style::new().bold().color(Color::White).bg(Color::Green));
+
+        let mut row = |left: Line, sep: &str, right: Line| {
+            let mut line = layout::truncate_end(&left, half);
+            if !right.is_empty() {
+                layout::pad_line(&mut line, half);
+                line.push(Text::new(format!(" {} ", sep)));
+                line.extend(layout::truncate_end(&right, half));
+            } else if sep != " " {
+                layout::pad_line(&mut line, half);
+                line.push(Text::new(format!(" {}", sep)));
+            }
+            out.push(line);
+        };
+
+        let mut k = 0;
+        while k < ops.len() {
+            if let Op::Equal(i, j) = ops[k] {
+                row(vec![Text::new(self.old[i].clone())], " ", vec![Text::new(self.new[j].clone())]);
+                k += 1;
+                continue;
+            }
+            // pair the deleted lines with the lines inserted after them
+            let mut dels = Vec::new();
+            let mut ins = Vec::new();
+            while k < ops.len() {
+                match ops[k] {
+                    Op::Delete(i) if ins.is_empty() => dels.push(i),
+                    Op::Insert(j) => ins.push(j),
+                    _ => break,
+                }
+                k += 1;
+            }
+            for n in 0..cmp::max(dels.len(), ins.len()) {
+                match (dels.get(n), ins.get(n)) {
+                    (Some(&i), Some(&j)) => {
+                        let (old, new) = (&self.old[i], &self.new[j]);
+                        let (old_words, new_words) = (words(old), words(new));
+                        let mut left = Vec::new();
+                        let mut right = Vec::new();
+                        for op in diff_ops(&old_words, &new_words) {
+                            match op {
+                                Op::Equal(a, b) => {
+                                    left.push(Text::new(old_words[a].into()).with_style(&deleted));
+                                    right.push(Text::new(new_words[b].into()).with_style(&inserted));
+                                }
+                                Op::Delete(a) => {
+                                    left.push(Text::new(old_words[a].into()).with_style(&deleted_word))
+                                }
+                                Op::Insert(b) => {
+                                    right.push(Text::new(new_words[b].into()).with_style(&inserted_word))
+                                }
+                            }
+                        }
+                        row(merge_texts(left), "|", merge_texts(right));
+                    }
+                    (Some(&i), None) => {
+                        row(vec![Text::new(self.old[i].clone()).with_style(&deleted)], "<", Vec::new());
+                    }
+                    (None, Some(&j)) => {
+                        row(Vec::new(), ">", vec![Text::new(self.new[j].clone()).with_style(&inserted)]);
+                    }
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+/// Merge neighbouring texts with the same style.
+fn merge_texts(texts: Vec<Text>) -> Line {
+    let mut out: Line = Vec::new();
+    for text in texts {
+        let merged = match out.last_mut() {
+            Some(last) if last.with_text(String::new()) == text.with_text(String::new()) => {
+                *last = last.with_text(format!("{}{}", last.get_text(), text.get_text()));
+                true
+            }
+            _ => false,
+        };
+        if !merged {
+            out.push(text);
+        }
+    }
+    out
+}
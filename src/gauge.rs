@@ -0,0 +1,278 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Progress bars and gauges.
+
+use std_prelude::*;
+
+use layout::{repeat, Line};
+use {Color, Style, Text};
+
+/// The partially filled blocks, from one eighth to seven eighths.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+#[derive(Debug, Clone)]
+/// A snapshot of a progress bar or gauge, i.e. `[#####-----]  50%`.
+///
+/// The gauge is a single line. Use [`texts`](#method.texts) to put it in a table cell.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let cell = |s: &str| vec![Text::new(s.into())];
+/// let mut table = Table::new(vec![
+///     vec![cell("/"), Gauge::new(0.5).width(10).texts()],
+///     vec![cell("/home"), Gauge::new(0.95).width(10).texts()],
+/// ]);
+/// table.set_plain();
+///
+/// let expected = "\
+/// /     [#####-----]  50%
+/// /home [##########]  95%
+/// ";
+///
+/// let mut result = Vec::new();
+/// table.paint(&mut result).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Gauge {
+    ratio: f64,
+    width: usize,
+    blocks: bool,
+    fill: char,
+    empty: char,
+    brackets: bool,
+    percent: bool,
+    label: Option<Vec<Text>>,
+    style: Style,
+    empty_style: Style,
+    thresholds: Vec<(f64, Style)>,
+}
+
+/// The bits of the value, with every NaN the same, so that floats can be compared with a total
+/// equality (NaN is equal to NaN, but `0.0` is not equal to `-0.0`).
+pub(crate) fn total_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+impl PartialEq for Gauge {
+    fn eq(&self, other: &Gauge) -> bool {
+        let thresholds = |g: &Gauge| -> Vec<(u64, Style)> {
+            g.thresholds
+                .iter()
+                .map(|&(at, style)| (total_bits(at), style))
+                .collect()
+        };
+        total_bits(self.ratio) == total_bits(other.ratio)
+            && self.width == other.width
+            && self.blocks == other.blocks
+            && self.fill == other.fill
+            && self.empty == other.empty
+            && self.brackets == other.brackets
+            && self.percent == other.percent
+            && self.label == other.label
+            && self.style == other.style
+            && self.empty_style == other.empty_style
+            && thresholds(self) == thresholds(other)
+    }
+}
+
+impl Eq for Gauge {}
+
+impl Gauge {
+    /// Create a gauge filled to `ratio` (between 0.0 and 1.0), labeled with its percentage.
+    ///
+    /// The ratio is clamped, NaN is treated as 0.0.
+    pub fn new(ratio: f64) -> Gauge {
+        let ratio = if ratio.is_nan() {
+            0.0
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+        Gauge {
+            ratio,
+            width: 20,
+            blocks: false,
+            fill: '#',
+            empty: '-',
+            brackets: true,
+            percent: true,
+            label: None,
+            style: Style::new(),
+            empty_style: Style::new(),
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Create a progress bar of `done` out of `total` steps, labeled with `done/total`.
+    pub fn progress(done: u64, total: u64) -> Gauge {
+        let ratio = if total == 0 {
+            1.0
+        } else {
+            done as f64 / total as f64
+        };
+        Gauge::new(ratio).label(vec![Text::new(format!("{}/{}", done, total))])
+    }
+
+    /// Set the width of the bar, excluding the brackets and label (default 20).
+    pub fn width(mut self, width: usize) -> Gauge {
+        self.width = width;
+        self
+    }
+
+    /// Draw the bar with unicode blocks (`█▌`), which show eighths of a column.
+    ///
+    /// The empty part is drawn with spaces unless `empty` is set after this, and there are no
+    /// brackets unless `brackets` is set after this.
+    pub fn blocks(mut self, blocks: bool) -> Gauge {
+        self.blocks = blocks;
+        if blocks {
+            self.empty = ' ';
+            self.brackets = false;
+        }
+        self
+    }
+
+    /// Set the glyph of the filled part (default `#`). Not used when drawing with blocks.
+    pub fn fill(mut self, fill: char) -> Gauge {
+        self.fill = fill;
+        self
+    }
+
+    /// Set the glyph of the empty part (default `-`).
+    pub fn empty(mut self, empty: char) -> Gauge {
+        self.empty = empty;
+        self
+    }
+
+    /// Whether to draw `[` and `]` around the bar.
+    pub fn brackets(mut self, brackets: bool) -> Gauge {
+        self.brackets = brackets;
+        self
+    }
+
+    /// Whether to show the percentage after the bar (default true). Hidden by a `label`.
+    pub fn percent(mut self, percent: bool) -> Gauge {
+        self.percent = percent;
+        self
+    }
+
+    /// Show the label after the bar instead of the percentage.
+    pub fn label(mut self, label: Vec<Text>) -> Gauge {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the style of the filled part.
+    pub fn style(mut self, style: Style) -> Gauge {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the empty part.
+    pub fn empty_style(mut self, style: Style) -> Gauge {
+        self.empty_style = style;
+        self
+    }
+
+    /// Use `style` for the filled part (and percentage) when the ratio is at least `ratio`.
+    ///
+    /// The style of the highest threshold that is reached replaces the gauge's own style.
+    pub fn threshold(mut self, ratio: f64, style: Style) -> Gauge {
+        self.thresholds.push((ratio, style));
+        self
+    }
+
+    /// Color the gauge green, then yellow from 70% and red from 90%.
+    pub fn utilization(self) -> Gauge {
+        self.style(Style::new().color(Color::Green))
+            .threshold(0.7, Style::new().color(Color::Yellow))
+            .threshold(0.9, Style::new().color(Color::Red))
+    }
+
+    pub fn get_ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Clears the formatting of the gauge.
+    pub fn set_plain(&mut self) {
+        if let Some(ref mut label) = self.label {
+            for t in label {
+                t.set_plain();
+            }
+        }
+        self.style = Style::new();
+        self.empty_style = Style::new();
+        self.thresholds.clear();
+    }
+
+    /// The style of the filled part, according to the thresholds.
+    fn fill_style(&self) -> Style {
+        self.thresholds
+            .iter()
+            .filter(|&&(t, _)| self.ratio >= t)
+            .max_by(|a, b| a.0.partial_cmp(&b.0).expect("thresholds are not NaN"))
+            .map(|&(_, s)| s)
+            .unwrap_or(self.style)
+    }
+
+    /// The gauge as texts, i.e. to put it in a table cell.
+    pub fn texts(&self) -> Vec<Text> {
+        let style = self.fill_style();
+        let (filled, partial) = if self.blocks {
+            let eighths = (self.ratio * (self.width * 8) as f64).round() as usize;
+            let partial = match eighths % 8 {
+                0 => None,
+                n => Some(EIGHTHS[n - 1]),
+            };
+            (eighths / 8, partial)
+        } else {
+            ((self.ratio * self.width as f64).round() as usize, None)
+        };
+        let empty = self.width - filled - partial.map(|_| 1).unwrap_or(0);
+
+        let mut texts = Vec::new();
+        if self.brackets {
+            texts.push(Text::new("[".into()));
+        }
+        let mut bar = repeat(if self.blocks { '█' } else { self.fill }, filled);
+        bar.extend(partial);
+        if !bar.is_empty() {
+            texts.push(Text::new(bar).with_style(&style));
+        }
+        if empty > 0 {
+            texts.push(Text::new(repeat(self.empty, empty)).with_style(&self.empty_style));
+        }
+        if self.brackets {
+            texts.push(Text::new("]".into()));
+        }
+        match self.label {
+            Some(ref label) => {
+                texts.push(Text::new(" ".into()));
+                texts.extend(label.iter().cloned());
+            }
+            None if self.percent => {
+                let percent = format!(" {:>3.0}%", self.ratio * 100.0);
+                texts.push(Text::new(percent).with_style(&style));
+            }
+            None => {}
+        }
+        texts
+    }
+
+    pub(crate) fn lines(&self) -> Vec<Line> {
+        vec![self.texts()]
+    }
+}
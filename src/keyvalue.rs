@@ -0,0 +1,159 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Key/value pairs (definition lists) with aligned keys.
+
+use std_prelude::*;
+
+use block::{inline_lines, DEFAULT_WIDTH};
+use layout::{self, repeat, Line};
+use {Style, Text};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Where to put text which is narrower than its column. Default is right.
+pub enum Align {
+    Left,
+    #[default]
+    Right,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// Lines of `key: value` pairs, with the keys padded so the separators line up.
+///
+/// Values which don't fit in the width are wrapped, their lines after the first are lined up with
+/// the first.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let t = |s: &str| vec![Text::new(s.into())];
+/// let mut kv = KeyValue::new()
+///     .pair(t("Name"), t("termstyle"))
+///     .pair(t("Description"), t("create and test the style of text in your terminal"));
+/// kv.set_plain();
+///
+/// let expected = "\
+/// \x20      Name: termstyle
+/// Description: create and test
+///              the style of text
+///              in your terminal
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::KeyValue(kv)], 30).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct KeyValue {
+    pairs: Vec<(Vec<Text>, Vec<Text>)>,
+    separator: String,
+    key_style: Style,
+    align: Align,
+}
+
+impl Default for KeyValue {
+    fn default() -> KeyValue {
+        KeyValue {
+            pairs: Vec::new(),
+            separator: ": ".into(),
+            key_style: Style::new().bold(),
+            align: Align::default(),
+        }
+    }
+}
+
+impl KeyValue {
+    /// Create an empty list, add the pairs with `pair`.
+    pub fn new() -> KeyValue {
+        KeyValue::default()
+    }
+
+    /// Add a pair below the others.
+    pub fn pair(mut self, key: Vec<Text>, value: Vec<Text>) -> KeyValue {
+        self.pairs.push((key, value));
+        self
+    }
+
+    /// Set the separator painted between the keys and values (default `": "`).
+    pub fn separator<S: Into<String>>(mut self, separator: S) -> KeyValue {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Set the style applied on top of the keys (bold by default).
+    pub fn key_style(mut self, style: Style) -> KeyValue {
+        self.key_style = style;
+        self
+    }
+
+    /// Set how the keys are aligned (right by default, putting the separators right after them).
+    pub fn align(mut self, align: Align) -> KeyValue {
+        self.align = align;
+        self
+    }
+
+    pub fn get_pairs(&self) -> &[(Vec<Text>, Vec<Text>)] {
+        &self.pairs
+    }
+
+    /// Clears the formatting of the keys and values.
+    pub fn set_plain(&mut self) {
+        for t in self.pairs
+            .iter_mut()
+            .flat_map(|p| p.0.iter_mut().chain(p.1.iter_mut()))
+        {
+            t.set_plain();
+        }
+        self.key_style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let width = width.unwrap_or(DEFAULT_WIDTH);
+        let key_width = self.pairs
+            .iter()
+            .map(|p| layout::line_width(&p.0))
+            .max()
+            .unwrap_or(0);
+        let indent = key_width + layout::str_width(&self.separator);
+        let value_width = width.saturating_sub(indent);
+
+        let mut out = Vec::new();
+        for (key, value) in &self.pairs {
+            let pad = Text::new(repeat(' ', key_width - layout::line_width(key)));
+            let mut line = Vec::new();
+            if self.align == Align::Right {
+                line.push(pad.clone());
+            }
+            line.extend(key.iter().map(|t| t.clone().with_style(&self.key_style)));
+            if self.align == Align::Left {
+                line.push(pad);
+            }
+            line.push(Text::new(self.separator.clone()));
+
+            let mut values = inline_lines(value)
+                .iter()
+                .flat_map(|l| layout::wrap_line(l, value_width))
+                .collect::<Vec<_>>()
+                .into_iter();
+            line.extend(values.next().unwrap_or_default());
+            out.push(line);
+            for value in values {
+                let mut line = Vec::new();
+                if !value.is_empty() {
+                    line.push(Text::new(repeat(' ', indent)));
+                    line.extend(value);
+                }
+                out.push(line);
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,303 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Lines of styled text and the helpers used to measure and lay them out.
+//!
+//! Block elements (i.e. tables) are rendered into `Line`s before being painted, so that each
+//! `Text` is painted (and reset) on its own and styles can never bleed into padding or borders.
+
+use std::cmp;
+use std::env;
+use std::io;
+use std::mem;
+use std_prelude::*;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+#[cfg(unix)]
+use libc;
+
+use {Style, Text, Theme};
+
+/// A single line of styled text. Must not contain any `\n`.
+pub(crate) type Line = Vec<Text>;
+
+/// Get the width of the terminal.
+///
+/// Uses the `COLUMNS` environment variable if it is set, otherwise asks the terminal attached to
+/// stdout, stderr or stdin. Returns `None` if the width could not be determined (i.e. the output is
+/// piped to a file).
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// # fn main() {
+/// let width = termstyle::term_width().unwrap_or(80);
+/// # let _ = width;
+/// # }
+/// ```
+pub fn term_width() -> Option<usize> {
+    parse_columns(env::var("COLUMNS").ok()).or_else(tty_width)
+}
+
+/// Parse the value of the `COLUMNS` environment variable.
+///
+/// Returns `None` if the value is missing, not a number or zero.
+pub(crate) fn parse_columns(value: Option<String>) -> Option<usize> {
+    value
+        .and_then(|c| c.trim().parse().ok())
+        .filter(|&cols| cols > 0)
+}
+
+#[cfg(unix)]
+fn tty_width() -> Option<usize> {
+    for fd in &[libc::STDOUT_FILENO, libc::STDERR_FILENO, libc::STDIN_FILENO] {
+        // SAFETY: `winsize` is plain old data and is only written to by the ioctl.
+        let size = unsafe {
+            let mut size: libc::winsize = mem::zeroed();
+            if libc::ioctl(*fd, libc::TIOCGWINSZ, &mut size) != 0 {
+                continue;
+            }
+            size
+        };
+        if size.ws_col > 0 {
+            return Some(size.ws_col as usize);
+        }
+    }
+    None
+}
+
+#[cfg(not(unix))]
+fn tty_width() -> Option<usize> {
+    // TODO: no terminal size for non-unix systems
+    None
+}
+
+/// The number of terminal columns the string takes up.
+pub(crate) fn str_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// The number of terminal columns the line takes up.
+pub(crate) fn line_width(line: &[Text]) -> usize {
+    line.iter().map(|t| str_width(t.get_text())).sum()
+}
+
+/// The character repeated `n` times.
+pub(crate) fn repeat(c: char, n: usize) -> String {
+    c.to_string().repeat(n)
+}
+
+/// Pad the line with spaces until it is `width` wide.
+pub(crate) fn pad_line(line: &mut Line, width: usize) {
+    pad_line_styled(line, width, &Style::new())
+}
+
+/// Pad the line with spaces in the given style (i.e. with a background) until it is `width` wide.
+pub(crate) fn pad_line_styled(line: &mut Line, width: usize, style: &Style) {
+    let w = line_width(line);
+    if w < width {
+        line.push(Text::new(repeat(' ', width - w)).with_style(style));
+    }
+}
+
+/// Split the texts into lines at each `\n`, keeping the style of every piece.
+pub(crate) fn split_lines(texts: &[Text]) -> Vec<Line> {
+    let mut lines = vec![Vec::new()];
+    for text in texts {
+        for (i, piece) in text.get_text().split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !piece.is_empty() {
+                lines.last_mut().unwrap().push(text.with_text(piece.into()));
+            }
+        }
+    }
+    lines
+}
+
+/// Paint the lines, ending each one with a newline.
+pub(crate) fn paint_lines<W: io::Write>(w: &mut W, lines: &[Line]) -> io::Result<()> {
+    paint_lines_themed(w, lines, &Theme::default())
+}
+
+/// Paint the lines like `paint_lines`, styling the roles of the texts with the theme.
+pub(crate) fn paint_lines_themed<W: io::Write>(
+    w: &mut W,
+    lines: &[Line],
+    theme: &Theme,
+) -> io::Result<()> {
+    for line in lines {
+        for text in line {
+            text.paint_themed(w, theme)?;
+        }
+        w.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Split the line so that the first part is at most `width` columns wide.
+///
+/// At least one character is always taken (unless the line is empty), so splitting a line made of
+/// wide characters always makes progress.
+pub(crate) fn split_at_width(line: &[Text], width: usize) -> (Line, Line) {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut used = 0;
+    for text in line {
+        if !tail.is_empty() {
+            tail.push(text.clone());
+            continue;
+        }
+        let mut split = None;
+        for (i, c) in text.get_text().char_indices() {
+            let cw = UnicodeWidthChar::width(c).unwrap_or(0);
+            if used + cw > width && (used > 0 || i > 0) {
+                split = Some(i);
+                break;
+            }
+            used += cw;
+        }
+        match split {
+            None => head.push(text.clone()),
+            Some(i) => {
+                let (h, t) = text.get_text().split_at(i);
+                if !h.is_empty() {
+                    head.push(text.with_text(h.into()));
+                }
+                tail.push(text.with_text(t.into()));
+            }
+        }
+    }
+    (head, tail)
+}
+
+/// Truncate the line to `width` columns, ending it with `…` if anything was cut.
+pub(crate) fn truncate_end(line: &[Text], width: usize) -> Line {
+    if line_width(line) <= width {
+        return line.to_vec();
+    }
+    if width == 0 {
+        return Vec::new();
+    }
+    let (mut head, tail) = split_at_width(line, width - 1);
+    if line_width(&head) > width - 1 {
+        head.clear();
+    }
+    let cut = tail.first().or_else(|| line.last()).expect("line is not empty");
+    head.push(cut.with_text("…".into()));
+    head
+}
+
+/// Truncate the line to `width` columns by replacing its middle with `…`.
+///
+/// Useful for paths, where both the start and the end are important.
+pub(crate) fn truncate_middle(line: &[Text], width: usize) -> Line {
+    let total = line_width(line);
+    if total <= width {
+        return line.to_vec();
+    }
+    if width < 3 {
+        return truncate_end(line, width);
+    }
+    let right = (width - 1) / 2;
+    let left = width - 1 - right;
+    let (mut head, rest) = split_at_width(line, left);
+    let (_, mut tail) = split_at_width(&rest, total - right - line_width(&head));
+    while line_width(&tail) > right {
+        tail = split_at_width(&tail, 1).1;
+    }
+    head.push(rest[0].with_text("…".into()));
+    head.extend(tail);
+    head
+}
+
+/// Wrap the line at word boundaries so that each line is at most `width` columns wide.
+///
+/// Words which are longer than `width` are split. The whitespace at a wrap point is dropped, all
+/// other text keeps its style.
+pub(crate) fn wrap_line(line: &[Text], width: usize) -> Vec<Line> {
+    wrap_line_hanging(line, width, width)
+}
+
+/// Wrap the line like `wrap_line`, but with the first line at most `first` columns wide and the
+/// others at most `rest` columns wide.
+pub(crate) fn wrap_line_hanging(line: &[Text], first: usize, rest: usize) -> Vec<Line> {
+    let (first, rest) = (cmp::max(first, 1), cmp::max(rest, 1));
+
+    // Each word is preceded by the whitespace before it.
+    let mut words: Vec<(Line, Line)> = Vec::new();
+    let mut space = Vec::new();
+    let mut word = Vec::new();
+    for text in line {
+        let s = text.get_text();
+        let mut start = 0;
+        while start < s.len() {
+            let is_space = s[start..].starts_with(' ');
+            let end = s[start..]
+                .find(|c| (c == ' ') != is_space)
+                .map(|e| start + e)
+                .unwrap_or_else(|| s.len());
+            let run = text.with_text(s[start..end].into());
+            if is_space {
+                if !word.is_empty() {
+                    words.push((mem::take(&mut space), mem::take(&mut word)));
+                }
+                space.push(run);
+            } else {
+                word.push(run);
+            }
+            start = end;
+        }
+    }
+    if !word.is_empty() || !space.is_empty() {
+        words.push((space, word));
+    }
+
+    let mut lines = Vec::new();
+    let mut current: Line = Vec::new();
+    for (space, mut word) in words {
+        let width = if lines.is_empty() { first } else { rest };
+        let used = line_width(&current);
+        if used + line_width(&space) + line_width(&word) <= width {
+            current.extend(space);
+            current.extend(word);
+            continue;
+        }
+        if used > 0 {
+            lines.push(mem::take(&mut current));
+        }
+        loop {
+            let width = if lines.is_empty() { first } else { rest };
+            if line_width(&word) <= width {
+                break;
+            }
+            let (head, tail) = split_at_width(&word, width);
+            lines.push(head);
+            word = tail;
+        }
+        current = word;
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_columns_values() {
+        assert_eq!(Some(42), parse_columns(Some("42".into())));
+        assert_eq!(Some(42), parse_columns(Some(" 42\n".into())));
+        assert_eq!(None, parse_columns(Some("0".into())));
+        assert_eq!(None, parse_columns(Some("wide".into())));
+        assert_eq!(None, parse_columns(None));
+    }
+}
@@ -46,24 +46,77 @@
 //! See the documentation for [`Table`](struct.Table.html)
 
 extern crate ansi_term;
+extern crate csv;
+#[cfg(unix)]
+extern crate libc;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 extern crate std_prelude;
-extern crate tabwriter;
+extern crate unicode_width;
+
+mod block;
+mod chart;
+mod code;
+mod columns;
+mod convert;
+mod diagnostic;
+mod diff;
+mod gauge;
+mod keyvalue;
+mod layout;
+mod list;
+mod panel;
+mod stream;
+mod stylesheet;
+mod table;
+mod theme;
+mod tree;
+mod wrap;
 
 use std::io;
 use std_prelude::*;
 use ansi_term::Color as AColor;
 
+pub use block::{Heading, Paragraph, Rule};
+pub use chart::{BarChart, Sparkline};
+pub use code::{Code, CodeTheme, Highlighter, Lexer, Token};
+pub use columns::{Columns, Width};
+pub use diagnostic::{Diagnostic, Label, Severity};
+pub use diff::Diff;
+pub use gauge::Gauge;
+pub use keyvalue::{Align, KeyValue};
+pub use layout::term_width;
+pub use list::List;
+pub use panel::{Callout, Panel};
+pub use stream::{TableStream, WideRows};
+pub use stylesheet::Stylesheet;
+pub use table::{Border, CellRule, Column, ColumnRef, Overflow, Sort, Table, VAlign};
+pub use theme::{Role, Theme};
+pub use tree::{Node, Tree};
+pub use wrap::Wrap;
+
 /// Convert a string into `Vec<El>` using the given deserializer.
 pub fn from_str<E, F>(_from_str: F, s: &str) -> Result<Vec<El>, E>
+where
+    F: Fn(&str) -> Result<Vec<ElRaw>, E>,
+{
+    from_str_styled(_from_str, s, &Stylesheet::new())
+}
+
+/// Convert a string into `Vec<El>` using the given deserializer, resolving the `class` of texts
+/// with the stylesheet.
+///
+/// The `styles` items of the document are added to the stylesheet for the items of their list,
+/// see [`Stylesheet`](struct.Stylesheet.html).
+pub fn from_str_styled<E, F>(_from_str: F, s: &str, styles: &Stylesheet) -> Result<Vec<El>, E>
 where
     F: Fn(&str) -> Result<Vec<ElRaw>, E>,
 {
     let raw: Vec<ElRaw> = _from_str(s)?;
     let mut out: Vec<El> = Vec::new();
-    flatten_raw(&mut out, raw);
+    flatten_raw(&mut out, raw, styles);
     Ok(out)
 }
 
@@ -73,6 +126,11 @@ where
 /// `Vec` and want to paint them all in one go.
 ///
 /// Alternatively, try calling the `paint()` method on the types themselves, (i.e. `El::paint()`).
+///
+/// Blocks are laid out in 80 columns, use [`paint_term`](fn.paint_term.html) to fit them to the
+/// terminal or [`paint_width`](fn.paint_width.html) to pick the width.
+///
+/// The roles of texts are styled with the default (dark) theme, use `Theme::paint` to pick another.
 pub fn paint<W: io::Write>(w: &mut W, items: &[El]) -> io::Result<()> {
     for item in items {
         item.paint(w)?;
@@ -80,10 +138,29 @@ pub fn paint<W: io::Write>(w: &mut W, items: &[El]) -> io::Result<()> {
     Ok(())
 }
 
+/// Paint the given elements into the writer, laying out blocks (and fitting tables) in `width`
+/// columns.
+pub fn paint_width<W: io::Write>(w: &mut W, items: &[El], width: usize) -> io::Result<()> {
+    for item in items {
+        item.paint_width(w, width)?;
+    }
+    Ok(())
+}
+
+/// Paint the given elements into the writer, laying out blocks (and fitting tables) in the width
+/// of the terminal.
+///
+/// The width is looked up once with [`term_width`](fn.term_width.html), falling back to 80
+/// columns if the output is not a terminal.
+pub fn paint_term<W: io::Write>(w: &mut W, items: &[El]) -> io::Result<()> {
+    paint_width(w, items, term_width().unwrap_or(80))
+}
+
 /// Helper function to make tests easier for others.
 ///
-/// If a diff exists, render the full form of both and their "repr" version to stderr, then return
-/// their human readable and copy-pastable renderings.
+/// If a diff exists, render the full form of both and their "repr" version to stderr, followed by
+/// a unified diff of their "repr" lines, then return their human readable and copy-pastable
+/// renderings.
 ///
 /// This is useful for testing, as you can clearly see the differences.
 ///
@@ -131,6 +208,23 @@ pub fn eprint_diff(expected: &[u8], result: &[u8]) -> (String, String) {
     eprint_repr(result);
     eprintln!();
 
+    eprintln!("\n## DIFF");
+    let repr_lines = |bytes: &[u8]| {
+        bytes
+            .split(|&b| b == b'\n')
+            .map(|line| {
+                let mut repr = Vec::new();
+                write_repr(&mut repr, line).unwrap();
+                String::from_utf8(repr).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let diff = Diff::new(repr_lines(expected), repr_lines(result)).names("expected", "result");
+    let mut rendered = Vec::new();
+    paint(&mut rendered, &[El::Diff(diff)]).unwrap();
+    eprint!("{}", String::from_utf8_lossy(&rendered));
+
     (
         String::from_utf8(expected_repr).unwrap(),
         String::from_utf8(result_repr).unwrap(),
@@ -184,57 +278,36 @@ pub fn eprint_repr(bytes: &[u8]) {
     write_repr(&mut io::stderr(), bytes).expect("eprint_repr");
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 /// A Element that can be rendered as styled+formatted text using `paint()`.
 ///
 /// Elements are simply struts with various properties which you can build directly or parse from
 /// text.
+///
+/// `Text` is painted inline, all other elements are blocks which are painted as whole lines (each
+/// ending with a newline) and are laid out in 80 columns (see
+/// [`paint_width`](fn.paint_width.html) and [`paint_term`](fn.paint_term.html)).
+// Boxing the variants would make matching on and building elements harder.
+#[allow(clippy::large_enum_variant)]
 pub enum El {
     Text(Text),
     Table(Table),
-}
-
-#[derive(Debug, Eq, PartialEq)]
-/// A paintable Table
-///
-/// The type can be thought of as `Rows[Cols[Cells[Text]]]`, where the items inside a `Cell`
-/// will be concatenated together (alowing mixed formatting to exist within a table's cell).
-///
-/// Warning: do not use `\t` in your text, as this currently uses tabwriter under the hood.
-///
-/// # Examples
-/// ```rust
-/// # extern crate termstyle;
-/// use termstyle::*;
-///
-/// # fn main() {
-/// let rows = vec![
-///     // header
-///     vec![
-///         vec![Text::new("header1".into())],
-///         vec![Text::new("header2".into())],
-///     ],
-///     // row1
-///     vec![
-///         vec![Text::new("col1".into())],
-///         vec![Text::new("col2".into())],
-///     ],
-/// ];
-/// let example = Table::new(rows);
-///
-/// let expected = "\
-/// header1 header2
-/// col1    col2
-/// ";
-///
-/// let mut result = Vec::new();
-/// example.paint(&mut result);
-///
-/// assert_eq!(expected.as_bytes(), result.as_slice());
-/// # }
-/// ```
-pub struct Table {
-    table: Vec<Vec<Vec<Text>>>,
+    Heading(Heading),
+    Rule(Rule),
+    /// The elements, indented by the given number of spaces.
+    Indent(usize, Vec<El>),
+    Paragraph(Paragraph),
+    List(List),
+    Panel(Panel),
+    Columns(Columns),
+    Tree(Tree),
+    KeyValue(KeyValue),
+    Gauge(Gauge),
+    Sparkline(Sparkline),
+    BarChart(BarChart),
+    Code(Code),
+    Diagnostic(Diagnostic),
+    Diff(Diff),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -255,13 +328,29 @@ pub enum Color {
     // RGB(u8, u8, u8),
 }
 
-#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 /// A piece of text, may be colored, etc
 pub struct Text {
     t: String, // 'text'
     b: bool,   // 'bold'
     i: bool,   // 'italic'
+    d: bool,   // 'dim'
+    c: Color,  // 'color'
+    bg: Color, // 'background color'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<Role>, // styled by the theme when painted
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// A style without any text, used to style many `Text`s at once (i.e. a table header).
+///
+/// Uses the same keys as `Text` when deserialized.
+pub struct Style {
+    b: bool,   // 'bold'
+    i: bool,   // 'italic'
+    d: bool,   // 'dim'
     c: Color,  // 'color'
     bg: Color, // 'background color'
 }
@@ -305,55 +394,76 @@ impl El {
         match *self {
             El::Text(ref mut t) => t.set_plain(),
             El::Table(ref mut t) => t.set_plain(),
+            El::Heading(ref mut h) => h.set_plain(),
+            El::Rule(ref mut r) => r.set_plain(),
+            El::Indent(_, ref mut els) => for el in els {
+                el.set_plain();
+            },
+            El::Paragraph(ref mut p) => p.set_plain(),
+            El::List(ref mut l) => l.set_plain(),
+            El::Panel(ref mut p) => p.set_plain(),
+            El::Columns(ref mut c) => c.set_plain(),
+            El::Tree(ref mut t) => t.set_plain(),
+            El::KeyValue(ref mut kv) => kv.set_plain(),
+            El::Gauge(ref mut g) => g.set_plain(),
+            El::Sparkline(ref mut s) => s.set_plain(),
+            El::BarChart(ref mut c) => c.set_plain(),
+            El::Code(ref mut c) => c.set_plain(),
+            El::Diagnostic(ref mut d) => d.set_plain(),
+            El::Diff(ref mut d) => d.set_plain(),
         }
     }
 
-    /// Paint (render) the item into the writer.
+    /// Paint (render) the item into the writer, laying out blocks in 80 columns.
     pub fn paint<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        match *self {
-            El::Text(ref t) => t.paint(w),
-            El::Table(ref t) => t.paint(w),
-        }
+        self.paint_with(w, None, &Theme::default())
     }
-}
 
-impl Table {
-    /// Create a new table from the given rows.
-    ///
-    /// The type can be thought of as `Rows[Cols[Cells[Text]]]`, where the items inside a `Cell`
-    /// will be concatenated together (alowing mixed formatting to exist within a table's cell).
-    pub fn new(table: Vec<Vec<Vec<Text>>>) -> Table {
-        Table { table: table }
+    /// Paint (render) the item into the writer, laying it out in `width` columns.
+    pub fn paint_width<W: io::Write>(&self, w: &mut W, width: usize) -> io::Result<()> {
+        self.paint_with(w, Some(width), &Theme::default())
     }
 
-    /// Recursively clears _all_ formatting.
-    pub fn set_plain(&mut self) {
-        for row in &mut self.table {
-            for col in row {
-                for t in col {
-                    t.set_plain();
-                }
-            }
+    /// Paint (render) the item into the writer, laying it out in the width of the terminal (80
+    /// columns if it is not a terminal).
+    pub fn paint_term<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.paint_width(w, term_width().unwrap_or(80))
+    }
+
+    /// Paint the item laid out in `width` (or the default width), styling roles with the theme.
+    pub(crate) fn paint_with<W: io::Write>(
+        &self,
+        w: &mut W,
+        width: Option<usize>,
+        theme: &Theme,
+    ) -> io::Result<()> {
+        match *self {
+            El::Text(ref t) => t.paint_themed(w, theme),
+            _ => layout::paint_lines_themed(w, &self.lines(width), theme),
         }
     }
 
-    /// Paint the table, giving each column the same width.
-    pub fn paint<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        // println!("Painting table:\n{:#?}\n", self);
-        let mut tw = tabwriter::TabWriter::new(Vec::new()).padding(1);
-        for row in &self.table {
-            for (i, cell) in row.iter().enumerate() {
-                for text in cell {
-                    text.paint(&mut tw)?;
-                }
-                if i < row.len() - 1 {
-                    write!(&mut tw, "\t")?;
-                }
-            }
-            write!(&mut tw, "\n")?;
+    /// The lines of the element, laid out in `width` (or the default width).
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<layout::Line> {
+        match *self {
+            El::Text(ref t) => block::inline_lines(::std::slice::from_ref(t)),
+            El::Table(ref t) => t.lines(width),
+            El::Heading(ref h) => h.lines(width),
+            El::Rule(ref r) => r.lines(width),
+            El::Indent(indent, ref els) => block::indent_lines(indent, els, width),
+            El::Paragraph(ref p) => p.lines(width),
+            El::List(ref l) => l.lines(width),
+            El::Panel(ref p) => p.lines(width),
+            El::Columns(ref c) => c.lines(width),
+            El::Tree(ref t) => t.lines(width),
+            El::KeyValue(ref kv) => kv.lines(width),
+            El::Gauge(ref g) => g.lines(),
+            El::Sparkline(ref s) => s.lines(),
+            El::BarChart(ref c) => c.lines(width),
+            El::Code(ref c) => c.lines(width),
+            El::Diagnostic(ref d) => d.lines(),
+            El::Diff(ref d) => d.lines(width),
         }
-        tw.flush()?;
-        w.write_all(&tw.into_inner().unwrap())
     }
 }
 
@@ -381,8 +491,10 @@ impl Text {
             t: t,
             b: false,
             i: false,
+            d: false,
             c: Color::default(),
             bg: Color::default(),
+            role: None,
         }
     }
 
@@ -398,27 +510,73 @@ impl Text {
         self
     }
 
+    /// Make the text styled as dim (faint)
+    pub fn dim(mut self) -> Text {
+        self.d = true;
+        self
+    }
+
     /// Set the color style of the text
     pub fn color(mut self, color: Color) -> Text {
         self.c = color;
         self
     }
 
+    /// Set the background color of the text
+    pub fn bg(mut self, color: Color) -> Text {
+        self.bg = color;
+        self
+    }
+
+    /// Set the role of the text, which is styled by the theme when painted.
+    ///
+    /// The text's own attributes are applied on top of the role's style.
+    pub fn role(mut self, role: Role) -> Text {
+        self.role = Some(role);
+        self
+    }
+
+    /// Apply the style on top of the text's own style.
+    ///
+    /// Attributes set in the style are added and colors set in the style replace the text's own.
+    pub fn with_style(mut self, style: &Style) -> Text {
+        style.apply(&mut self);
+        self
+    }
+
+    /// The text's own style, without its role.
+    fn own_style(&self) -> Style {
+        Style {
+            b: self.b,
+            i: self.i,
+            d: self.d,
+            c: self.c,
+            bg: self.bg,
+        }
+    }
+
     #[cfg(unix)]
-    fn style(&self) -> ansi_term::Style {
+    fn style(&self, theme: &Theme) -> ansi_term::Style {
+        let own = match self.role {
+            Some(role) => theme.get(role).with_style(&self.own_style()),
+            None => self.own_style(),
+        };
         let mut style = ansi_term::Style::new();
-        if self.b {
+        if own.b {
             style = style.bold();
         }
-        if self.i {
+        if own.i {
             style = style.italic();
         }
-        style = match self.c.to_ansi() {
+        if own.d {
+            style = style.dimmed();
+        }
+        style = match own.c.to_ansi() {
             None => style,
             Some(c) => style.fg(c),
         };
 
-        style = match self.bg.to_ansi() {
+        style = match own.bg.to_ansi() {
             None => style,
             Some(c) => style.on(c),
         };
@@ -426,13 +584,19 @@ impl Text {
     }
 
     #[cfg(not(unix))]
-    fn style(&self) -> ansi_term::Style {
+    fn style(&self, _theme: &Theme) -> ansi_term::Style {
         // TODO: no style for non-unix systems
         ansi_term::Style::new()
     }
 
+    /// Paint the text, styling its role with the default (dark) theme.
     pub fn paint<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
-        let style = self.style();
+        self.paint_themed(w, &Theme::default())
+    }
+
+    /// Paint the text, styling its role with the theme.
+    pub fn paint_themed<W: io::Write>(&self, w: &mut W, theme: &Theme) -> io::Result<()> {
+        let style = self.style(theme);
         write!(w, "{}", style.paint(self.t.as_str()))
     }
 
@@ -444,6 +608,10 @@ impl Text {
         self.i
     }
 
+    pub fn is_dim(&self) -> bool {
+        self.d
+    }
+
     pub fn is_plain(&self) -> bool {
         self.c == Color::Plain
     }
@@ -452,12 +620,105 @@ impl Text {
         self.c
     }
 
+    pub fn get_bg(&self) -> Color {
+        self.bg
+    }
+
+    pub fn get_role(&self) -> Option<Role> {
+        self.role
+    }
+
+    /// The raw (unstyled) text.
+    pub fn get_text(&self) -> &str {
+        &self.t
+    }
+
+    /// A copy of this text with the same style but different contents.
+    pub(crate) fn with_text(&self, t: String) -> Text {
+        Text { t, ..self.clone() }
+    }
+
     /// Clears _all_ formatting.
     pub fn set_plain(&mut self) {
         self.b = false;
         self.i = false;
+        self.d = false;
         self.c = Color::Plain;
         self.bg = Color::Plain;
+        self.role = None;
+    }
+}
+
+impl Style {
+    /// Create a style with no attributes set.
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Make the style bold
+    pub fn bold(mut self) -> Style {
+        self.b = true;
+        self
+    }
+
+    /// Make the style italic
+    pub fn italic(mut self) -> Style {
+        self.i = true;
+        self
+    }
+
+    /// Make the style dim (faint)
+    pub fn dim(mut self) -> Style {
+        self.d = true;
+        self
+    }
+
+    /// Set the color of the style
+    pub fn color(mut self, color: Color) -> Style {
+        self.c = color;
+        self
+    }
+
+    /// Set the background color of the style
+    pub fn bg(mut self, color: Color) -> Style {
+        self.bg = color;
+        self
+    }
+
+    /// Apply the other style on top of this one.
+    ///
+    /// Attributes set in `other` are added and colors set in `other` replace this style's own.
+    pub fn with_style(mut self, other: &Style) -> Style {
+        self.b |= other.b;
+        self.i |= other.i;
+        self.d |= other.d;
+        if other.c != Color::Plain {
+            self.c = other.c;
+        }
+        if other.bg != Color::Plain {
+            self.bg = other.bg;
+        }
+        self
+    }
+
+    /// Whether the style has no attributes set.
+    pub fn is_plain(&self) -> bool {
+        *self == Style::default()
+    }
+
+    /// Apply the style on top of the text's own style.
+    ///
+    /// See [`Text::with_style`](struct.Text.html#method.with_style).
+    pub fn apply(&self, text: &mut Text) {
+        text.b |= self.b;
+        text.i |= self.i;
+        text.d |= self.d;
+        if self.c != Color::Plain {
+            text.c = self.c;
+        }
+        if self.bg != Color::Plain {
+            text.bg = self.bg;
+        }
     }
 }
 
@@ -467,15 +728,276 @@ impl Text {
 #[serde(untagged)]
 /// Raw `El` type, used only for deserializing.
 pub enum ElRaw {
-    // This MUST be first
-    Table(TableRaw),
+    // These MUST be before `Text`, which accepts any map
+    Table(Box<TableRaw>),
+    Heading {
+        heading: TextsRaw,
+        #[serde(default)]
+        level: Option<usize>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Rule {
+        /// The character to draw the rule with, `─` if empty.
+        rule: String,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Indent {
+        indent: usize,
+        items: Vec<ElRaw>,
+    },
+    Paragraph {
+        paragraph: TextsRaw,
+        #[serde(default)]
+        indent: Option<usize>,
+        #[serde(default)]
+        hanging: Option<usize>,
+        #[serde(default)]
+        justify: Option<bool>,
+    },
+    List {
+        list: Vec<ItemRaw>,
+        #[serde(default)]
+        ordered: Option<bool>,
+        #[serde(default)]
+        start: Option<usize>,
+        #[serde(default)]
+        bullets: Option<Vec<String>>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Panel {
+        panel: ItemRaw,
+        #[serde(default)]
+        callout: Option<Callout>,
+        #[serde(default)]
+        title: Option<TextsRaw>,
+        #[serde(default)]
+        border: Option<Border>,
+        #[serde(default)]
+        border_style: Option<Style>,
+        #[serde(default)]
+        title_style: Option<Style>,
+        #[serde(default)]
+        padding: Option<usize>,
+    },
+    Columns {
+        columns: Vec<ItemRaw>,
+        /// The width of each block, `{ratio: 1}` if missing.
+        #[serde(default)]
+        widths: Vec<Width>,
+        #[serde(default)]
+        gap: Option<usize>,
+    },
+    Tree {
+        tree: Vec<NodeRaw>,
+        #[serde(default)]
+        ascii: Option<bool>,
+        #[serde(default)]
+        guide_style: Option<Style>,
+        #[serde(default)]
+        max_depth: Option<usize>,
+    },
+    KeyValue {
+        key_value: Vec<(TextsRaw, TextsRaw)>,
+        #[serde(default)]
+        separator: Option<String>,
+        #[serde(default)]
+        key_style: Option<Style>,
+        #[serde(default)]
+        align: Option<Align>,
+    },
+    Gauge {
+        /// The ratio, between 0.0 and 1.0.
+        gauge: f64,
+        #[serde(default)]
+        width: Option<usize>,
+        #[serde(default)]
+        blocks: Option<bool>,
+        #[serde(default)]
+        fill: Option<char>,
+        #[serde(default)]
+        empty: Option<char>,
+        #[serde(default)]
+        brackets: Option<bool>,
+        #[serde(default)]
+        percent: Option<bool>,
+        #[serde(default)]
+        label: Option<TextsRaw>,
+        #[serde(default)]
+        style: Option<Style>,
+        #[serde(default)]
+        empty_style: Option<Style>,
+        /// `[ratio, style]` pairs.
+        #[serde(default)]
+        thresholds: Vec<(f64, Style)>,
+    },
+    Sparkline {
+        sparkline: Vec<f64>,
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+        #[serde(default)]
+        ascii: Option<bool>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    BarChart {
+        /// `[label, value]` pairs.
+        bar_chart: Vec<(TextsRaw, f64)>,
+        #[serde(default)]
+        width: Option<usize>,
+        #[serde(default)]
+        max: Option<f64>,
+        #[serde(default)]
+        precision: Option<usize>,
+        #[serde(default)]
+        ascii: Option<bool>,
+        #[serde(default)]
+        style: Option<Style>,
+    },
+    Code {
+        code: String,
+        #[serde(default)]
+        language: String,
+        #[serde(default)]
+        line_numbers: Option<bool>,
+        #[serde(default)]
+        first_line: Option<usize>,
+        #[serde(default)]
+        gutter: Option<bool>,
+        /// `[first, last]` line ranges.
+        #[serde(default)]
+        marks: Vec<(usize, usize)>,
+        #[serde(default)]
+        theme: Option<CodeTheme>,
+    },
+    Diagnostic {
+        diagnostic: TextsRaw,
+        #[serde(default)]
+        severity: Severity,
+        #[serde(default)]
+        code: Option<String>,
+        #[serde(default)]
+        path: Option<String>,
+        #[serde(default)]
+        source: String,
+        #[serde(default)]
+        first_line: Option<usize>,
+        #[serde(default)]
+        labels: Vec<LabelRaw>,
+        #[serde(default)]
+        notes: Vec<TextsRaw>,
+        #[serde(default)]
+        help: Vec<TextsRaw>,
+    },
+    Diff {
+        /// `[old, new]`
+        diff: (String, String),
+        #[serde(default)]
+        names: Option<(String, String)>,
+        #[serde(default)]
+        context: Option<usize>,
+        #[serde(default)]
+        side_by_side: Option<bool>,
+    },
+    /// Classes for the texts of the document, see `Stylesheet`.
+    Styles {
+        styles: Stylesheet,
+    },
     Text(TextsRaw),
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+/// Raw diagnostic label, i.e. `{line: 3, col: 5, len: 4, label: "..."}`
+pub struct LabelRaw {
+    line: usize,
+    col: usize,
+    #[serde(default)]
+    len: usize,
+    #[serde(default)]
+    label: Option<TextsRaw>,
+    #[serde(default)]
+    secondary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+/// Raw tree node, used so you can specify `"foo"` or `{label: "foo", tree: [...]}`
+pub enum NodeRaw {
+    // This MUST be first
+    Branch {
+        label: TextsRaw,
+        #[serde(default)]
+        tree: Vec<NodeRaw>,
+        #[serde(default)]
+        collapsed: bool,
+    },
+    Leaf(TextsRaw),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+/// Raw list item or panel content, used so you can specify `"foo"` or `["foo\n", {list: [...]}]`
+pub enum ItemRaw {
+    Multi(Vec<ElRaw>),
+    Single(Box<ElRaw>),
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Raw `Table` type, used only for deserializing.
 pub struct TableRaw {
-    table: Vec<Vec<TextsRaw>>,
+    table: Vec<Vec<CellRaw>>,
+    #[serde(default)]
+    header: Option<Vec<CellRaw>>,
+    /// Multiple header rows, painted after `header`.
+    #[serde(default)]
+    headers: Vec<Vec<CellRaw>>,
+    #[serde(default)]
+    footer: Option<Vec<CellRaw>>,
+    #[serde(default)]
+    border: Option<Border>,
+    #[serde(default)]
+    header_style: Option<Style>,
+    #[serde(default)]
+    footer_style: Option<Style>,
+    #[serde(default)]
+    border_style: Option<Style>,
+    #[serde(default)]
+    separators: Option<bool>,
+    #[serde(default)]
+    columns: Option<Vec<Column>>,
+    #[serde(default)]
+    valign: Option<VAlign>,
+    #[serde(default)]
+    row_valign: BTreeMap<usize, VAlign>,
+    #[serde(default)]
+    gap: Option<usize>,
+    #[serde(default)]
+    padding: Option<usize>,
+    #[serde(default)]
+    indent: Option<usize>,
+    #[serde(default)]
+    stripe: Option<Style>,
+    #[serde(default)]
+    cell_rules: Vec<CellRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+/// Raw table cell, used so you can specify `{cell: "foo", colspan: 2}` or just `"foo"`
+pub enum CellRaw {
+    // This MUST be first
+    Spanned {
+        cell: TextsRaw,
+        #[serde(default)]
+        colspan: Option<usize>,
+        #[serde(default)]
+        rowspan: Option<usize>,
+    },
+    Plain(TextsRaw),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -491,52 +1013,540 @@ pub enum TextsRaw {
 /// Raw `Text` type, used only for deserializing.
 pub enum TextRaw {
     Simple(String),
+    // This MUST be before `Full`
+    Classed {
+        class: String,
+        #[serde(flatten)]
+        text: Text,
+    },
     Full(Text),
 }
 
-fn flatten_raw(into: &mut Vec<El>, mut raw: Vec<ElRaw>) {
+fn flatten_raw(into: &mut Vec<El>, mut raw: Vec<ElRaw>, styles: &Stylesheet) {
+    // the `styles` items apply to all the items of the list, including the ones before them
+    let mut scoped = styles.clone();
+    for el in &raw {
+        if let ElRaw::Styles { styles: ref defined } = *el {
+            scoped.extend(defined.clone());
+        }
+    }
     for el in raw.drain(..) {
-        flatten_el(into, el);
+        flatten_el(into, el, &scoped);
     }
 }
 
-fn flatten_el(into: &mut Vec<El>, raw: ElRaw) {
+fn flatten_el(into: &mut Vec<El>, raw: ElRaw, styles: &Stylesheet) {
     match raw {
-        ElRaw::Text(t) => flatten_texts(into, t),
-        ElRaw::Table(mut table_raw) => {
-            let mut table = Vec::new();
-            for mut row_raw in table_raw.table.drain(..) {
-                let mut row = Vec::new();
-                for cell_raw in row_raw.drain(..) {
-                    let mut cell = Vec::new();
-                    flatten_texts_only(&mut cell, cell_raw);
-                    row.push(cell);
+        // already added to the stylesheet by `flatten_raw`
+        ElRaw::Styles { .. } => {}
+        ElRaw::Text(t) => flatten_texts(into, t, styles),
+        ElRaw::Heading {
+            heading,
+            level,
+            style,
+        } => {
+            let mut texts = Vec::new();
+            flatten_texts_only(&mut texts, heading, styles);
+            let mut h = Heading::new(level.unwrap_or(1), texts);
+            if let Some(style) = style {
+                h = h.style(style);
+            }
+            into.push(El::Heading(h));
+        }
+        ElRaw::Rule { rule, style } => {
+            let mut r = Rule::new();
+            if let Some(c) = rule.chars().next() {
+                r = r.c(c);
+            }
+            if let Some(style) = style {
+                r = r.style(style);
+            }
+            into.push(El::Rule(r));
+        }
+        ElRaw::Indent { indent, items } => {
+            let mut els = Vec::new();
+            flatten_raw(&mut els, items, styles);
+            into.push(El::Indent(indent, els));
+        }
+        ElRaw::Paragraph {
+            paragraph,
+            indent,
+            hanging,
+            justify,
+        } => {
+            let mut texts = Vec::new();
+            flatten_texts_only(&mut texts, paragraph, styles);
+            let mut p = Paragraph::new(texts);
+            if let Some(indent) = indent {
+                p = p.indent(indent);
+            }
+            if let Some(hanging) = hanging {
+                p = p.hanging(hanging);
+            }
+            if let Some(justify) = justify {
+                p = p.justify(justify);
+            }
+            into.push(El::Paragraph(p));
+        }
+        ElRaw::List {
+            list,
+            ordered,
+            start,
+            bullets,
+            style,
+        } => {
+            let items = list.into_iter().map(|i| flatten_item(i, styles)).collect();
+            let mut l = List::new(items);
+            if let Some(ordered) = ordered {
+                l = l.ordered(ordered);
+            }
+            if let Some(start) = start {
+                l = l.start(start);
+            }
+            if let Some(bullets) = bullets {
+                l = l.bullets(bullets);
+            }
+            if let Some(style) = style {
+                l = l.style(style);
+            }
+            into.push(El::List(l));
+        }
+        ElRaw::Panel {
+            panel,
+            callout,
+            title,
+            border,
+            border_style,
+            title_style,
+            padding,
+        } => {
+            let content = flatten_item(panel, styles);
+            let mut p = match callout {
+                Some(kind) => Panel::callout(kind, content),
+                None => Panel::new(content),
+            };
+            if let Some(title) = title {
+                let mut texts = Vec::new();
+                flatten_texts_only(&mut texts, title, styles);
+                p = p.title(texts);
+            }
+            if let Some(border) = border {
+                p = p.border(border);
+            }
+            if let Some(style) = border_style {
+                p = p.border_style(style);
+            }
+            if let Some(style) = title_style {
+                p = p.title_style(style);
+            }
+            if let Some(padding) = padding {
+                p = p.padding(padding);
+            }
+            into.push(El::Panel(p));
+        }
+        ElRaw::Columns {
+            columns,
+            widths,
+            gap,
+        } => {
+            let mut c = Columns::new();
+            for (i, block) in columns.into_iter().enumerate() {
+                let width = widths.get(i).cloned().unwrap_or_default();
+                c = c.column(width, flatten_item(block, styles));
+            }
+            if let Some(gap) = gap {
+                c = c.gap(gap);
+            }
+            into.push(El::Columns(c));
+        }
+        ElRaw::Tree {
+            tree,
+            ascii,
+            guide_style,
+            max_depth,
+        } => {
+            let mut t = Tree::new(tree.into_iter().map(|n| flatten_node(n, styles)).collect());
+            if let Some(ascii) = ascii {
+                t = t.ascii(ascii);
+            }
+            if let Some(style) = guide_style {
+                t = t.guide_style(style);
+            }
+            if let Some(depth) = max_depth {
+                t = t.max_depth(depth);
+            }
+            into.push(El::Tree(t));
+        }
+        ElRaw::KeyValue {
+            key_value,
+            separator,
+            key_style,
+            align,
+        } => {
+            let mut kv = KeyValue::new();
+            for (key_raw, value_raw) in key_value {
+                let (mut key, mut value) = (Vec::new(), Vec::new());
+                flatten_texts_only(&mut key, key_raw, styles);
+                flatten_texts_only(&mut value, value_raw, styles);
+                kv = kv.pair(key, value);
+            }
+            if let Some(separator) = separator {
+                kv = kv.separator(separator);
+            }
+            if let Some(style) = key_style {
+                kv = kv.key_style(style);
+            }
+            if let Some(align) = align {
+                kv = kv.align(align);
+            }
+            into.push(El::KeyValue(kv));
+        }
+        ElRaw::Gauge {
+            gauge,
+            width,
+            blocks,
+            fill,
+            empty,
+            brackets,
+            percent,
+            label,
+            style,
+            empty_style,
+            thresholds,
+        } => {
+            let mut g = Gauge::new(gauge);
+            if let Some(width) = width {
+                g = g.width(width);
+            }
+            if let Some(blocks) = blocks {
+                g = g.blocks(blocks);
+            }
+            if let Some(fill) = fill {
+                g = g.fill(fill);
+            }
+            if let Some(empty) = empty {
+                g = g.empty(empty);
+            }
+            if let Some(brackets) = brackets {
+                g = g.brackets(brackets);
+            }
+            if let Some(percent) = percent {
+                g = g.percent(percent);
+            }
+            if let Some(label) = label {
+                let mut texts = Vec::new();
+                flatten_texts_only(&mut texts, label, styles);
+                g = g.label(texts);
+            }
+            if let Some(style) = style {
+                g = g.style(style);
+            }
+            if let Some(style) = empty_style {
+                g = g.empty_style(style);
+            }
+            for (ratio, style) in thresholds {
+                g = g.threshold(ratio, style);
+            }
+            into.push(El::Gauge(g));
+        }
+        ElRaw::Sparkline {
+            sparkline,
+            min,
+            max,
+            ascii,
+            style,
+        } => {
+            let mut s = Sparkline::new(sparkline);
+            if let Some(min) = min {
+                s = s.min(min);
+            }
+            if let Some(max) = max {
+                s = s.max(max);
+            }
+            if let Some(ascii) = ascii {
+                s = s.ascii(ascii);
+            }
+            if let Some(style) = style {
+                s = s.style(style);
+            }
+            into.push(El::Sparkline(s));
+        }
+        ElRaw::BarChart {
+            bar_chart,
+            width,
+            max,
+            precision,
+            ascii,
+            style,
+        } => {
+            let mut c = BarChart::new();
+            for (label_raw, value) in bar_chart {
+                let mut label = Vec::new();
+                flatten_texts_only(&mut label, label_raw, styles);
+                c = c.bar(label, value);
+            }
+            if let Some(width) = width {
+                c = c.width(width);
+            }
+            if let Some(max) = max {
+                c = c.max(max);
+            }
+            if let Some(precision) = precision {
+                c = c.precision(precision);
+            }
+            if let Some(ascii) = ascii {
+                c = c.ascii(ascii);
+            }
+            if let Some(style) = style {
+                c = c.style(style);
+            }
+            into.push(El::BarChart(c));
+        }
+        ElRaw::Code {
+            code,
+            language,
+            line_numbers,
+            first_line,
+            gutter,
+            marks,
+            theme,
+        } => {
+            let mut c = Code::new(language, code);
+            if let Some(theme) = theme {
+                c = c.theme(theme);
+            }
+            if let Some(line_numbers) = line_numbers {
+                c = c.line_numbers(line_numbers);
+            }
+            if let Some(first_line) = first_line {
+                c = c.first_line(first_line);
+            }
+            if let Some(gutter) = gutter {
+                c = c.gutter(gutter);
+            }
+            for (first, last) in marks {
+                c = c.mark_lines(first, last);
+            }
+            into.push(El::Code(c));
+        }
+        ElRaw::Diagnostic {
+            diagnostic,
+            severity,
+            code,
+            path,
+            source,
+            first_line,
+            labels,
+            notes,
+            help,
+        } => {
+            let mut message = Vec::new();
+            flatten_texts_only(&mut message, diagnostic, styles);
+            let mut d = Diagnostic::new(severity, message)
+                .source(&source, first_line.unwrap_or(1));
+            if let Some(code) = code {
+                d = d.code(code);
+            }
+            if let Some(path) = path {
+                d = d.path(path);
+            }
+            for label in labels {
+                let mut text = Vec::new();
+                if let Some(raw) = label.label {
+                    flatten_texts_only(&mut text, raw, styles);
                 }
-                table.push(row);
+                d = d.label(if label.secondary {
+                    Label::secondary(label.line, label.col, label.len, text)
+                } else {
+                    Label::primary(label.line, label.col, label.len, text)
+                });
+            }
+            for raw in notes {
+                let mut note = Vec::new();
+                flatten_texts_only(&mut note, raw, styles);
+                d = d.note(note);
+            }
+            for raw in help {
+                let mut help = Vec::new();
+                flatten_texts_only(&mut help, raw, styles);
+                d = d.help(help);
+            }
+            into.push(El::Diagnostic(d));
+        }
+        ElRaw::Diff {
+            diff: (old, new),
+            names,
+            context,
+            side_by_side,
+        } => {
+            let mut d = Diff::new(old, new);
+            if let Some((old, new)) = names {
+                d = d.names(old, new);
+            }
+            if let Some(context) = context {
+                d = d.context(context);
             }
-            into.push(El::Table(Table { table: table }));
+            if let Some(side_by_side) = side_by_side {
+                d = d.side_by_side(side_by_side);
+            }
+            into.push(El::Diff(d));
+        }
+        ElRaw::Table(table_raw) => {
+            let table_raw = *table_raw;
+            let mut rows = Vec::new();
+            let mut spans = Vec::new();
+            for (r, row_raw) in table_raw.table.into_iter().enumerate() {
+                rows.push(flatten_row(row_raw, r, &mut spans, styles));
+            }
+            let mut table = Table::new(rows);
+            for (r, c, colspan, rowspan) in spans.drain(..) {
+                table = table.span(r, c, colspan, rowspan);
+            }
+            let headers = table_raw.header.into_iter().chain(table_raw.headers);
+            for (r, row_raw) in headers.enumerate() {
+                table = table.header(flatten_row(row_raw, r, &mut spans, styles));
+            }
+            for (r, c, colspan, rowspan) in spans.drain(..) {
+                table = table.header_span(r, c, colspan, rowspan);
+            }
+            if let Some(footer) = table_raw.footer {
+                table = table.footer(flatten_row(footer, 0, &mut spans, styles));
+            }
+            for (r, c, colspan, rowspan) in spans.drain(..) {
+                table = table.footer_span(r, c, colspan, rowspan);
+            }
+            if let Some(border) = table_raw.border {
+                table = table.border(border);
+            }
+            if let Some(style) = table_raw.header_style {
+                table = table.header_style(style);
+            }
+            if let Some(style) = table_raw.footer_style {
+                table = table.footer_style(style);
+            }
+            if let Some(style) = table_raw.border_style {
+                table = table.border_style(style);
+            }
+            if let Some(separators) = table_raw.separators {
+                table = table.separators(separators);
+            }
+            if let Some(columns) = table_raw.columns {
+                table = table.columns(columns);
+            }
+            if let Some(valign) = table_raw.valign {
+                table = table.valign(valign);
+            }
+            for (row, valign) in table_raw.row_valign {
+                table = table.row_valign(row, valign);
+            }
+            if let Some(gap) = table_raw.gap {
+                table = table.gap(gap);
+            }
+            if let Some(padding) = table_raw.padding {
+                table = table.padding(padding);
+            }
+            if let Some(indent) = table_raw.indent {
+                table = table.indent(indent);
+            }
+            if let Some(stripe) = table_raw.stripe {
+                table = table.stripe(stripe);
+            }
+            for rule in table_raw.cell_rules {
+                table = table.cell_rule(rule);
+            }
+            into.push(El::Table(table));
         }
     }
 }
 
-fn flatten_texts(into: &mut Vec<El>, raw: TextsRaw) {
+fn flatten_item(raw: ItemRaw, styles: &Stylesheet) -> Vec<El> {
+    let mut els = Vec::new();
     match raw {
-        TextsRaw::Single(t) => into.push(El::Text(Text::from(t))),
-        TextsRaw::Multi(mut multi) => into.extend(multi.drain(..).map(|t| El::Text(Text::from(t)))),
+        ItemRaw::Multi(raw) => flatten_raw(&mut els, raw, styles),
+        ItemRaw::Single(raw) => flatten_el(&mut els, *raw, styles),
     }
+    els
 }
 
-fn flatten_texts_only(into: &mut Vec<Text>, raw: TextsRaw) {
+fn flatten_node(raw: NodeRaw, styles: &Stylesheet) -> Node {
+    let mut label = Vec::new();
     match raw {
-        TextsRaw::Single(t) => into.push(Text::from(t)),
-        TextsRaw::Multi(mut multi) => into.extend(multi.drain(..).map(Text::from)),
+        NodeRaw::Leaf(texts) => {
+            flatten_texts_only(&mut label, texts, styles);
+            Node::new(label)
+        }
+        NodeRaw::Branch {
+            label: texts,
+            tree,
+            collapsed,
+        } => {
+            flatten_texts_only(&mut label, texts, styles);
+            Node::new(label)
+                .children(tree.into_iter().map(|n| flatten_node(n, styles)).collect())
+                .collapsed(collapsed)
+        }
+    }
+}
+
+/// Flatten the row, pushing the `(row, cell, colspan, rowspan)` of spanning cells into `spans`.
+fn flatten_row(
+    row_raw: Vec<CellRaw>,
+    r: usize,
+    spans: &mut Vec<(usize, usize, usize, usize)>,
+    styles: &Stylesheet,
+) -> Vec<Vec<Text>> {
+    let mut row = Vec::new();
+    for (c, cell_raw) in row_raw.into_iter().enumerate() {
+        let mut cell = Vec::new();
+        match cell_raw {
+            CellRaw::Plain(texts) => flatten_texts_only(&mut cell, texts, styles),
+            CellRaw::Spanned {
+                cell: texts,
+                colspan,
+                rowspan,
+            } => {
+                flatten_texts_only(&mut cell, texts, styles);
+                spans.push((r, c, colspan.unwrap_or(1), rowspan.unwrap_or(1)));
+            }
+        }
+        row.push(cell);
+    }
+    row
+}
+
+fn flatten_texts(into: &mut Vec<El>, raw: TextsRaw, styles: &Stylesheet) {
+    let mut texts = Vec::new();
+    flatten_texts_only(&mut texts, raw, styles);
+    into.extend(texts.into_iter().map(El::Text));
+}
+
+fn flatten_texts_only(into: &mut Vec<Text>, raw: TextsRaw, styles: &Stylesheet) {
+    match raw {
+        TextsRaw::Single(t) => into.push(flatten_text(t, styles)),
+        TextsRaw::Multi(mut multi) => into.extend(multi.drain(..).map(|t| flatten_text(t, styles))),
+    }
+}
+
+/// Resolve the class of the text: the style of the class with the text's own on top.
+fn flatten_text(raw: TextRaw, styles: &Stylesheet) -> Text {
+    match raw {
+        TextRaw::Classed { class, text } => match styles.get(&class) {
+            Some(style) => {
+                let own = text.own_style();
+                text.with_style(style).with_style(&own)
+            }
+            None => text,
+        },
+        raw => Text::from(raw),
     }
 }
 
 impl From<TextRaw> for Text {
+    /// Convert the raw text, ignoring its class.
     fn from(raw: TextRaw) -> Text {
         match raw {
             TextRaw::Simple(t) => Text::new(t),
+            TextRaw::Classed { text, .. } => text,
             TextRaw::Full(f) => f,
         }
     }
@@ -0,0 +1,167 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Bulleted and numbered lists.
+
+use std_prelude::*;
+
+use block::{block_lines_with, DEFAULT_WIDTH};
+use layout::{self, repeat, Line};
+use {El, Style, Text};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// A bulleted (unordered) or numbered (ordered) list.
+///
+/// Each item is a `Vec<El>`, so it can hold multiple lines, tables or nested lists. The text of
+/// an item is wrapped, and lines after its first line are indented to line up with its text (a
+/// hanging indent). Numbers are right-aligned.
+///
+/// Nested lists use the next bullet glyph (`•`, `◦` and `▪` by default).
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let item = |s: &str| vec![El::plain(s.into())];
+/// let nested = List::new(vec![item("nested"), item("items")]);
+/// let list = List::new(vec![
+///     item("first"),
+///     item("second item, which is long enough to wrap"),
+///     vec![El::plain("third\n".into()), El::List(nested)],
+/// ]).ordered(true)
+///     .start(9);
+///
+/// let expected = "
+/// \x209. first
+/// 10. second item, which is
+///     long enough to wrap
+/// 11. third
+///     ◦ nested
+///     ◦ items
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::List(list)], 25).unwrap();
+/// assert_eq!(&expected[1..], String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct List {
+    items: Vec<Vec<El>>,
+    ordered: bool,
+    start: usize,
+    bullets: Vec<String>,
+    style: Style,
+}
+
+impl List {
+    /// Create a bulleted list.
+    pub fn new(items: Vec<Vec<El>>) -> List {
+        List {
+            items,
+            ordered: false,
+            start: 1,
+            bullets: vec!["•".into(), "◦".into(), "▪".into()],
+            style: Style::new(),
+        }
+    }
+
+    /// Number the items instead of using bullets.
+    pub fn ordered(mut self, ordered: bool) -> List {
+        self.ordered = ordered;
+        self
+    }
+
+    /// The number of the first item of an ordered list (default 1).
+    pub fn start(mut self, start: usize) -> List {
+        self.start = start;
+        self
+    }
+
+    /// The bullet glyphs to use for each level of nesting, repeating after the last one.
+    pub fn bullets(mut self, bullets: Vec<String>) -> List {
+        if !bullets.is_empty() {
+            self.bullets = bullets;
+        }
+        self
+    }
+
+    /// Set the style of the bullets or numbers.
+    pub fn style(mut self, style: Style) -> List {
+        self.style = style;
+        self
+    }
+
+    pub fn get_items(&self) -> &[Vec<El>] {
+        &self.items
+    }
+
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        for el in self.items.iter_mut().flat_map(|item| item.iter_mut()) {
+            el.set_plain();
+        }
+        self.style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        self.nested_lines(width.unwrap_or(DEFAULT_WIDTH), 0)
+    }
+
+    /// The lines of the list when nested in `depth` other lists.
+    fn nested_lines(&self, width: usize, depth: usize) -> Vec<Line> {
+        let markers: Vec<String> = (0..self.items.len())
+            .map(|i| {
+                if self.ordered {
+                    format!("{}.", self.start + i)
+                } else {
+                    self.bullets[depth % self.bullets.len()].clone()
+                }
+            })
+            .collect();
+        let marker_width = markers
+            .iter()
+            .map(|m| layout::str_width(m))
+            .max()
+            .unwrap_or(0);
+        let hang = marker_width + 1;
+        let inner = width.saturating_sub(hang);
+
+        let mut out = Vec::new();
+        for (item, marker) in self.items.iter().zip(markers) {
+            let mut lines = block_lines_with(item, Some(inner), |el| match *el {
+                El::List(ref list) => list.nested_lines(inner, depth + 1),
+                _ => el.lines(Some(inner)),
+            });
+            if lines.is_empty() {
+                lines.push(Vec::new());
+            }
+            for (i, line) in lines.iter_mut().enumerate() {
+                let prefix = if i == 0 {
+                    let pad = marker_width - layout::str_width(&marker);
+                    vec![
+                        Text::new(repeat(' ', pad)),
+                        Text::new(marker.clone()).with_style(&self.style),
+                        Text::new(" ".into()),
+                    ]
+                } else if line.is_empty() {
+                    continue;
+                } else {
+                    vec![Text::new(repeat(' ', hang))]
+                };
+                line.splice(0..0, prefix);
+            }
+            out.extend(lines);
+        }
+        out
+    }
+}
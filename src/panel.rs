@@ -0,0 +1,223 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Bordered panels (boxes) around other elements.
+
+use std_prelude::*;
+
+use block::{block_lines_with, DEFAULT_WIDTH};
+use layout::{self, repeat, Line};
+use {Border, Color, El, Style, Text};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The kinds of preset panels, like the admonitions in documentation.
+pub enum Callout {
+    /// Titled "Note", in blue.
+    Note,
+    /// Titled "Warning", in yellow.
+    Warning,
+    /// Titled "Error", in red.
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// A box drawn around other elements, with an optional title in its top border.
+///
+/// The panel is as wide as the width it is laid out in, the text of the content is wrapped to fit
+/// inside it. The borders are the same as the ones of [`Table`](struct.Table.html).
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let mut panel = Panel::warning(vec![
+///     El::plain("the config file is missing, using the defaults".into()),
+/// ]);
+/// panel.set_plain();
+///
+/// let expected = "\
+/// ╭─ Warning ──────────────╮
+/// │ the config file is     │
+/// │ missing, using the     │
+/// │ defaults               │
+/// ╰────────────────────────╯
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::Panel(panel)], 26).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Panel {
+    title: Vec<Text>,
+    content: Vec<El>,
+    border: Border,
+    border_style: Style,
+    title_style: Style,
+    padding: usize,
+}
+
+impl Panel {
+    /// Create a panel with a rounded border around the content.
+    pub fn new(content: Vec<El>) -> Panel {
+        Panel {
+            title: Vec::new(),
+            content,
+            border: Border::Rounded,
+            border_style: Style::new(),
+            title_style: Style::new().bold(),
+            padding: 1,
+        }
+    }
+
+    /// Create a preset panel, titled and colored according to its kind.
+    pub fn callout(kind: Callout, content: Vec<El>) -> Panel {
+        let (title, color) = match kind {
+            Callout::Note => ("Note", Color::Blue),
+            Callout::Warning => ("Warning", Color::Yellow),
+            Callout::Error => ("Error", Color::Red),
+        };
+        Panel::new(content)
+            .title(vec![Text::new(title.into())])
+            .title_style(Style::new().bold().color(color))
+            .border_style(Style::new().color(color))
+    }
+
+    /// Create a blue panel titled "Note".
+    pub fn note(content: Vec<El>) -> Panel {
+        Panel::callout(Callout::Note, content)
+    }
+
+    /// Create a yellow panel titled "Warning".
+    pub fn warning(content: Vec<El>) -> Panel {
+        Panel::callout(Callout::Warning, content)
+    }
+
+    /// Create a red panel titled "Error".
+    pub fn error(content: Vec<El>) -> Panel {
+        Panel::callout(Callout::Error, content)
+    }
+
+    /// Set the title, which is drawn in the top border.
+    pub fn title(mut self, title: Vec<Text>) -> Panel {
+        self.title = title;
+        self
+    }
+
+    /// Set the border. With `Border::None` the panel is drawn with spaces.
+    pub fn border(mut self, border: Border) -> Panel {
+        self.border = border;
+        self
+    }
+
+    /// Set the style (i.e. the color) of the border.
+    pub fn border_style(mut self, style: Style) -> Panel {
+        self.border_style = style;
+        self
+    }
+
+    /// Set the style applied on top of the title (bold by default).
+    pub fn title_style(mut self, style: Style) -> Panel {
+        self.title_style = style;
+        self
+    }
+
+    /// Set the number of spaces between the border and the content (default 1).
+    pub fn padding(mut self, padding: usize) -> Panel {
+        self.padding = padding;
+        self
+    }
+
+    pub fn get_title(&self) -> &[Text] {
+        &self.title
+    }
+
+    pub fn get_content(&self) -> &[El] {
+        &self.content
+    }
+
+    pub fn get_border(&self) -> Border {
+        self.border
+    }
+
+    pub fn get_padding(&self) -> usize {
+        self.padding
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        for t in &mut self.title {
+            t.set_plain();
+        }
+        for el in &mut self.content {
+            el.set_plain();
+        }
+        self.border_style = Style::new();
+        self.title_style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let width = width.unwrap_or(DEFAULT_WIDTH);
+        let boxed = self.border != Border::None;
+        let glyphs = self.border.glyphs();
+        let glyph = |s: String| Text::new(s).with_style(&self.border_style);
+        // the width between the vertical borders
+        let inside = width.saturating_sub(2);
+        let inner = inside.saturating_sub(2 * self.padding);
+        let title: Vec<Text> = self.title
+            .iter()
+            .map(|t| t.clone().with_style(&self.title_style))
+            .collect();
+
+        let mut out = Vec::new();
+        if boxed {
+            let mut top = vec![glyph(format!("{}", glyphs.top[0]))];
+            if !title.is_empty() && inside >= 5 {
+                top.push(glyph(format!("{} ", glyphs.h)));
+                top.extend(layout::truncate_end(&title, inside - 4));
+                top.push(Text::new(" ".into()));
+            }
+            let used = layout::line_width(&top) - 1;
+            top.push(glyph(repeat(glyphs.h, inside.saturating_sub(used))));
+            top.push(glyph(format!("{}", glyphs.top[2])));
+            out.push(top);
+        } else if !title.is_empty() {
+            let mut top = vec![Text::new(repeat(' ', 1 + self.padding))];
+            top.extend(layout::truncate_end(&title, inner));
+            out.push(top);
+        }
+
+        let content = block_lines_with(&self.content, Some(inner), |el| el.lines(Some(inner)));
+        for line in content {
+            let mut l = vec![Text::new(repeat(' ', 1 + self.padding))];
+            if boxed {
+                l[0] = glyph(format!("{}", glyphs.v));
+                l.push(Text::new(repeat(' ', self.padding)));
+            }
+            l.extend(layout::truncate_end(&line, inner));
+            if boxed {
+                layout::pad_line(&mut l, 1 + self.padding + inner);
+                l.push(Text::new(repeat(' ', self.padding)));
+                l.push(glyph(format!("{}", glyphs.v)));
+            }
+            out.push(l);
+        }
+
+        if boxed {
+            out.push(vec![glyph(format!(
+                "{}{}{}",
+                glyphs.bottom[0],
+                repeat(glyphs.h, inside),
+                glyphs.bottom[2]
+            ))]);
+        }
+        out
+    }
+}
@@ -0,0 +1,214 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Painting tables one row at a time.
+
+use std::cmp;
+use std::io;
+use std::mem;
+use std_prelude::*;
+
+use layout::paint_lines;
+use {Table, Text};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// What a [`TableStream`](struct.TableStream.html) does with rows that are wider than its columns.
+pub enum WideRows {
+    /// Keep the column widths, the cells are wrapped or truncated according to their column's
+    /// [`Overflow`](enum.Overflow.html).
+    #[default]
+    Fit,
+    /// Widen the columns to fit the row, for it and all following rows.
+    ///
+    /// The top border and header are already painted, so they keep the widths they were painted
+    /// with and no longer line up with the widened rows.
+    Widen,
+    /// Paint the row as wide as it needs to be (misaligning it), keeping the column widths for the
+    /// following rows.
+    Overflow,
+}
+
+/// Paint a table one row at a time, i.e. for rows which arrive over time or don't fit in memory.
+///
+/// The header, footer and settings (border, columns, styles, etc) are taken from a `Table`. The
+/// column widths are either fixed with [`widths`](#method.widths) or measured from the first
+/// [`sample`](#method.sample) rows, which are buffered until the widths are known. After that each
+/// row is painted (and the writer flushed) as soon as it is written.
+///
+/// The footer and bottom border are painted by [`finish`](#method.finish).
+///
+/// A row span (see [`Table::span`](struct.Table.html#method.span)) is painted once its last row
+/// has been written, the rows it covers are buffered until then. A span reaching past the last
+/// row is cut short when the stream is finished. With
+/// [`WideRows::Widen`](enum.WideRows.html#variant.Widen) the rows painted after the columns are
+/// widened don't line up with the header above them.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let cell = |s: &str| vec![Text::new(s.into())];
+/// let mut table = Table::new(Vec::new())
+///     .header(vec![cell("level"), cell("message")])
+///     .border(Border::Ascii);
+/// table.set_plain();
+///
+/// let mut stream = TableStream::new(table, Vec::new())
+///     .widths(vec![5, 12])
+///     .wide_rows(WideRows::Fit);
+/// stream.write_row(vec![cell("INFO"), cell("started")]).unwrap();
+/// stream.write_row(vec![cell("WARN"), cell("disk is almost full")]).unwrap();
+/// let result = stream.finish().unwrap();
+///
+/// let expected = "\
+/// +-------+--------------+
+/// | level | message      |
+/// +-------+--------------+
+/// | INFO  | started      |
+/// | WARN  | disk is      |
+/// |       | almost full  |
+/// +-------+--------------+
+/// ";
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TableStream<W: io::Write> {
+    table: Table,
+    w: W,
+    widths: Option<Vec<usize>>,
+    sample: usize,
+    wide_rows: WideRows,
+    started: bool,
+    /// The rows which have not been painted yet.
+    pending: Vec<Vec<Vec<Text>>>,
+    /// The number of rows painted so far.
+    painted: usize,
+}
+
+impl<W: io::Write> TableStream<W> {
+    /// Create a stream painting to `w`, using the header, footer and settings of `table`.
+    ///
+    /// The rows already in `table` are the first rows of the stream.
+    pub fn new(mut table: Table, w: W) -> TableStream<W> {
+        let pending = table.take_rows();
+        TableStream {
+            table,
+            w,
+            widths: None,
+            sample: 20,
+            wide_rows: WideRows::default(),
+            started: false,
+            pending,
+            painted: 0,
+        }
+    }
+
+    /// Use fixed widths for the columns, instead of measuring them.
+    ///
+    /// The widths exclude the padding. Columns without a width are measured from the header,
+    /// footer and the rows written before the first one is painted, or else from the first row
+    /// that has them.
+    pub fn widths(mut self, widths: Vec<usize>) -> TableStream<W> {
+        self.widths = Some(widths);
+        self
+    }
+
+    /// Measure the column widths from the header, footer and the first `rows` rows (default 20).
+    ///
+    /// Nothing is painted until `rows` rows have been written (or the stream is finished).
+    pub fn sample(mut self, rows: usize) -> TableStream<W> {
+        self.sample = cmp::max(rows, 1);
+        self
+    }
+
+    /// Set what to do with rows that are wider than the columns. See
+    /// [`WideRows`](enum.WideRows.html).
+    pub fn wide_rows(mut self, wide_rows: WideRows) -> TableStream<W> {
+        self.wide_rows = wide_rows;
+        self
+    }
+
+    /// Write a row, painting it as soon as the column widths are known (and any row span it is
+    /// part of is complete).
+    pub fn write_row(&mut self, row: Vec<Vec<Text>>) -> io::Result<()> {
+        self.pending.push(row);
+        if self.started || self.widths.is_some() || self.pending.len() >= self.sample {
+            self.paint_pending(false)?;
+        }
+        Ok(())
+    }
+
+    /// Write every row of the iterator. See [`write_row`](#method.write_row).
+    pub fn write_rows<I>(&mut self, rows: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Vec<Vec<Text>>>,
+    {
+        for row in rows {
+            self.write_row(row)?;
+        }
+        Ok(())
+    }
+
+    /// Paint any buffered rows, the footer and the bottom border, returning the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.paint_pending(true)?;
+        let widths = self.widths.take().unwrap_or_default();
+        paint_lines(&mut self.w, &self.table.foot_lines(&widths))?;
+        self.w.flush()?;
+        Ok(self.w)
+    }
+
+    /// Paint the pending rows, keeping the ones in an incomplete row span unless `finish`.
+    fn paint_pending(&mut self, finish: bool) -> io::Result<()> {
+        if !self.started {
+            // fixed widths may not cover every column, measure the ones missing
+            let measured = self.table.measure(0, &self.pending);
+            let mut widths = self.widths.take().unwrap_or_default();
+            if widths.len() < measured.len() {
+                widths.extend_from_slice(&measured[widths.len()..]);
+            }
+            paint_lines(&mut self.w, &self.table.head_lines(&widths))?;
+            self.widths = Some(widths);
+            self.started = true;
+        }
+        let count = if finish {
+            self.pending.len()
+        } else {
+            self.table.unspanned_rows(self.painted, &self.pending)
+        };
+        if count == 0 {
+            return self.w.flush();
+        }
+
+        let rest = self.pending.split_off(count);
+        let rows = mem::replace(&mut self.pending, rest);
+        let natural = self.table.measure(self.painted, &rows);
+        let widths = self.widths.get_or_insert_with(Vec::new);
+        for (i, &width) in natural.iter().enumerate() {
+            if i >= widths.len() {
+                widths.push(width);
+            } else if self.wide_rows == WideRows::Widen {
+                widths[i] = cmp::max(widths[i], width);
+            }
+        }
+        let mut row_widths = widths.clone();
+        if self.wide_rows == WideRows::Overflow {
+            for (w, &width) in row_widths.iter_mut().zip(&natural) {
+                *w = cmp::max(*w, width);
+            }
+        }
+
+        let lines = self.table.body_lines(&row_widths, self.painted, &rows);
+        paint_lines(&mut self.w, &lines)?;
+        self.painted += rows.len();
+        self.w.flush()
+    }
+}
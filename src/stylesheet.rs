@@ -0,0 +1,78 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Named styles, referred to by the texts of a document.
+
+use std::collections::BTreeMap;
+use std_prelude::*;
+
+use Style;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+/// Named styles (classes) which texts refer to with `class` when deserialized.
+///
+/// A document can define classes with a `styles` item, i.e. `- styles: {error: {b: true, c: red}}`,
+/// which are added to (or replace) the classes given to
+/// [`from_str_styled`](fn.from_str_styled.html). A `styles` item applies to every item of the
+/// list it is in (i.e. the items of an `indent` or a `panel`) and to the items nested in them,
+/// but not to the items outside of its list. The class is resolved when the document is
+/// flattened: the text gets the style of its class, with its own attributes applied on top.
+///
+/// Unknown classes are ignored, so a misspelled class leaves the text with only its own style.
+///
+/// # Examples
+/// ```rust
+/// # extern crate serde_yaml;
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let styles = Stylesheet::new().class("error", Style::new().bold().color(Color::Red));
+/// let yaml = r#"
+/// - styles:
+///     ok: {c: green}
+/// - {t: "failed", class: error}
+/// - " "
+/// - {t: "passed", class: ok, b: true}
+/// "#;
+/// let els = from_str_styled(serde_yaml::from_str, yaml, &styles).unwrap();
+///
+/// let expected = vec![
+///     El::Text(Text::new("failed".into()).bold().color(Color::Red)),
+///     El::plain(" ".into()),
+///     El::Text(Text::new("passed".into()).bold().color(Color::Green)),
+/// ];
+/// assert_eq!(expected, els);
+/// # }
+/// ```
+pub struct Stylesheet {
+    classes: BTreeMap<String, Style>,
+}
+
+impl Stylesheet {
+    /// Create a stylesheet without any classes.
+    pub fn new() -> Stylesheet {
+        Stylesheet::default()
+    }
+
+    /// Add the class, replacing a class of the same name.
+    pub fn class<S: Into<String>>(mut self, name: S, style: Style) -> Stylesheet {
+        self.classes.insert(name.into(), style);
+        self
+    }
+
+    /// Add the classes of the other stylesheet, replacing classes of the same name.
+    pub fn extend(&mut self, other: Stylesheet) {
+        self.classes.extend(other.classes);
+    }
+
+    /// Get the style of the class.
+    pub fn get(&self, name: &str) -> Option<&Style> {
+        self.classes.get(name)
+    }
+}
@@ -0,0 +1,1696 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Styled tables.
+
+use std::cmp::{self, Ordering};
+use std::fmt;
+use std::io;
+use std::mem;
+use std_prelude::*;
+
+use layout::{self, repeat, Line};
+use {Style, Text};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+/// A paintable Table
+///
+/// The type can be thought of as `Rows[Cols[Cells[Text]]]`, where the items inside a `Cell`
+/// will be concatenated together (alowing mixed formatting to exist within a table's cell).
+///
+/// Cells can contain multiple lines (i.e. text with `\n`), in which case the row is as tall as its
+/// tallest cell and the other cells are aligned according to the row's [`VAlign`](enum.VAlign.html).
+///
+/// A table can optionally have a header and a footer row. These are painted with their own style
+/// (bold and dim by default) and are divided from the body by a separator line.
+///
+/// Tables with style hooks (see [`row_style`](#method.row_style)) are only equal if they share
+/// the same hooks, i.e. one is a clone of the other. Two tables built with identical closures
+/// compare unequal.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let rows = vec![
+///     // header
+///     vec![
+///         vec![Text::new("header1".into())],
+///         vec![Text::new("header2".into())],
+///     ],
+///     // row1
+///     vec![
+///         vec![Text::new("col1".into())],
+///         vec![Text::new("col2".into())],
+///     ],
+/// ];
+/// let example = Table::new(rows);
+///
+/// let expected = "\
+/// header1 header2
+/// col1    col2
+/// ";
+///
+/// let mut result = Vec::new();
+/// example.paint(&mut result);
+///
+/// assert_eq!(expected.as_bytes(), result.as_slice());
+/// # }
+/// ```
+///
+/// With an explicit header and a border:
+///
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let mut example = Table::new(vec![
+///     vec![vec![Text::new("col1".into())], vec![Text::new("col2".into())]],
+/// ])
+///     .header(vec![
+///         vec![Text::new("header1".into())],
+///         vec![Text::new("header2".into())],
+///     ])
+///     .border(Border::Ascii);
+///
+/// example.set_plain();
+/// let expected = "\
+/// +---------+---------+
+/// | header1 | header2 |
+/// +---------+---------+
+/// | col1    | col2    |
+/// +---------+---------+
+/// ";
+///
+/// let mut result = Vec::new();
+/// example.paint(&mut result);
+///
+/// assert_eq!(expected.as_bytes(), result.as_slice());
+/// # }
+/// ```
+pub struct Table {
+    table: Vec<Vec<Vec<Text>>>,
+    header: Vec<Vec<Vec<Text>>>,
+    footer: Vec<Vec<Vec<Text>>>,
+    header_style: Style,
+    footer_style: Style,
+    border: Border,
+    border_style: Style,
+    separators: bool,
+    columns: Vec<Column>,
+    valign: VAlign,
+    row_valign: BTreeMap<usize, VAlign>,
+    spans: BTreeMap<(Part, usize, usize), (usize, usize)>,
+    gap: usize,
+    padding: Option<usize>,
+    indent: usize,
+    stripe: Option<Style>,
+    cell_rules: Vec<CellRule>,
+    hooks: StyleHooks,
+}
+
+/// A function deciding the style of a row from its index and cells.
+type RowHook = Arc<dyn Fn(usize, &[Vec<Text>]) -> Option<Style> + Send + Sync>;
+
+/// A function deciding the style of a cell from its row index, index in the row and texts.
+type CellHook = Arc<dyn Fn(usize, usize, &[Text]) -> Option<Style> + Send + Sync>;
+
+#[derive(Clone, Default)]
+/// The row and cell style hooks of a table.
+struct StyleHooks {
+    rows: Vec<RowHook>,
+    cells: Vec<CellHook>,
+}
+
+impl fmt::Debug for StyleHooks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StyleHooks {{ rows: {}, cells: {} }}",
+            self.rows.len(),
+            self.cells.len()
+        )
+    }
+}
+
+impl PartialEq for StyleHooks {
+    fn eq(&self, other: &StyleHooks) -> bool {
+        self.rows.len() == other.rows.len() && self.cells.len() == other.cells.len()
+            && self.rows.iter().zip(&other.rows).all(|(a, b)| Arc::ptr_eq(a, b))
+            && self.cells.iter().zip(&other.cells).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for StyleHooks {}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+/// A declarative rule styling the cells of a column which match a condition.
+///
+/// The rule's style is applied on top of the cell's own style. If neither `equals` nor
+/// `contains` is set then every cell in the column matches.
+///
+/// In yaml: `{column: status, equals: FAIL, style: {b: true, c: red}}`
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let cell = |s: &str| vec![Text::new(s.into())];
+/// let table = Table::new(vec![
+///     vec![cell("build"), cell("OK")],
+///     vec![cell("test"), cell("FAIL")],
+/// ])
+///     .header(vec![cell("step"), cell("status")])
+///     .cell_rule(
+///         CellRule::new("status")
+///             .equals("FAIL")
+///             .style(Style::new().bold().color(Color::Red)),
+///     );
+/// # let _ = table;
+/// # }
+/// ```
+pub struct CellRule {
+    column: ColumnRef,
+    #[serde(default)]
+    equals: Option<String>,
+    #[serde(default)]
+    contains: Option<String>,
+    #[serde(default)]
+    style: Style,
+    #[serde(default)]
+    row: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+/// A reference to a column, either by its index or by the text of its header.
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for ColumnRef {
+    fn from(index: usize) -> ColumnRef {
+        ColumnRef::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for ColumnRef {
+    fn from(name: &'a str) -> ColumnRef {
+        ColumnRef::Name(name.into())
+    }
+}
+
+impl From<String> for ColumnRef {
+    fn from(name: String) -> ColumnRef {
+        ColumnRef::Name(name)
+    }
+}
+
+impl ColumnRef {
+    /// The index of the column, looking up names in the (last) header row.
+    pub(crate) fn index(&self, header: Option<&Vec<Vec<Text>>>) -> Option<usize> {
+        match *self {
+            ColumnRef::Index(i) => Some(i),
+            ColumnRef::Name(ref name) => header.and_then(|h| {
+                h.iter()
+                    .position(|cell| plain_text(cell).trim() == name.as_str())
+            }),
+        }
+    }
+}
+
+impl CellRule {
+    /// Create a rule matching every cell of the column.
+    pub fn new<C: Into<ColumnRef>>(column: C) -> CellRule {
+        CellRule {
+            column: column.into(),
+            equals: None,
+            contains: None,
+            style: Style::new(),
+            row: false,
+        }
+    }
+
+    /// Only match cells whose text is exactly `text`.
+    pub fn equals<S: Into<String>>(mut self, text: S) -> CellRule {
+        self.equals = Some(text.into());
+        self
+    }
+
+    /// Only match cells whose text contains `text`.
+    pub fn contains<S: Into<String>>(mut self, text: S) -> CellRule {
+        self.contains = Some(text.into());
+        self
+    }
+
+    /// Set the style applied to the matching cells.
+    pub fn style(mut self, style: Style) -> CellRule {
+        self.style = style;
+        self
+    }
+
+    /// Style the whole row of a matching cell, instead of just the cell.
+    pub fn whole_row(mut self) -> CellRule {
+        self.row = true;
+        self
+    }
+
+    fn matches(&self, cell: &[Text]) -> bool {
+        let text = plain_text(cell);
+        self.equals.as_ref().map(|e| text == *e).unwrap_or(true)
+            && self.contains
+                .as_ref()
+                .map(|c| text.contains(c.as_str()))
+                .unwrap_or(true)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+/// The parts of a table, which are laid out as separate grids.
+enum Part {
+    Header,
+    Body,
+    Footer,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// Settings for a single column of a `Table`.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let cell = |s: &str| vec![Text::new(s.into())];
+/// let example = Table::new(vec![
+///     vec![cell("/usr/local/share/termstyle"), cell("a long description")],
+/// ])
+///     .column(0, Column::new().max_width(12).overflow(Overflow::TruncateMiddle))
+///     .column(1, Column::new().max_width(11));
+///
+/// let expected = "\
+/// /usr/l…style a long
+///              description
+/// ";
+///
+/// let mut result = Vec::new();
+/// example.paint(&mut result);
+///
+/// assert_eq!(expected.as_bytes(), result.as_slice());
+/// # }
+/// ```
+pub struct Column {
+    max_width: Option<usize>,
+    min_width: Option<usize>,
+    priority: u32,
+    overflow: Overflow,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Where to put the lines of a cell which is shorter than the other cells in its row.
+pub enum VAlign {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// How to compare the plain text of cells when sorting a table.
+pub enum Sort {
+    /// Compare the text as is.
+    #[default]
+    Lexicographic,
+    /// Compare the text as numbers. Text which is not a number (including `NaN`) sorts after all
+    /// numbers.
+    Numeric,
+    /// Compare runs of digits as numbers, i.e. `file2` sorts before `file10`.
+    Natural,
+}
+
+impl Sort {
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        match *self {
+            Sort::Lexicographic => a.cmp(b),
+            Sort::Numeric => {
+                let parse = |s: &str| s.trim().parse::<f64>().ok().filter(|v| !v.is_nan());
+                match (parse(a), parse(b)) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => a.cmp(b),
+                }
+            }
+            Sort::Natural => {
+                let (a, b) = (digit_runs(a), digit_runs(b));
+                for (x, y) in a.iter().zip(&b) {
+                    let order = if x.starts_with(|c: char| c.is_ascii_digit())
+                        && y.starts_with(|c: char| c.is_ascii_digit())
+                    {
+                        let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                        x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+                    } else {
+                        x.cmp(y)
+                    };
+                    if order != Ordering::Equal {
+                        return order;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+        }
+    }
+}
+
+/// Split the text into alternating runs of digits and other characters.
+fn digit_runs(s: &str) -> Vec<&str> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut digits = None;
+    for (i, c) in s.char_indices() {
+        let is_digit = c.is_ascii_digit();
+        if digits.is_some() && digits != Some(is_digit) {
+            runs.push(&s[start..i]);
+            start = i;
+        }
+        digits = Some(is_digit);
+    }
+    if start < s.len() {
+        runs.push(&s[start..]);
+    }
+    runs
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// What to do with cells that are wider than their column's `max_width`.
+///
+/// All policies keep the styles of the text, i.e. a wrapped bold span is bold on both lines.
+pub enum Overflow {
+    /// Wrap the cell onto multiple lines at word boundaries.
+    #[default]
+    Wrap,
+    /// Cut off the end of the cell, replacing it with `…`.
+    Truncate,
+    /// Cut out the middle of the cell, replacing it with `…`. Useful for paths.
+    TruncateMiddle,
+}
+
+impl Column {
+    /// Create the default column settings: no maximum width and `Overflow::Wrap`.
+    pub fn new() -> Column {
+        Column::default()
+    }
+
+    /// Set the maximum width of the column.
+    pub fn max_width(mut self, width: usize) -> Column {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set the minimum width of the column.
+    ///
+    /// When fitting the table into a target width (see
+    /// [`Table::paint_width`](struct.Table.html#method.paint_width)) the column will never be
+    /// shrunk below this width. Note that the column will still be narrower if none of its cells
+    /// are this wide.
+    pub fn min_width(mut self, width: usize) -> Column {
+        self.min_width = Some(width);
+        self
+    }
+
+    /// Set the priority of the column. Default is 0.
+    ///
+    /// When fitting the table into a target width, columns with a lower priority are shrunk
+    /// first.
+    pub fn priority(mut self, priority: u32) -> Column {
+        self.priority = priority;
+        self
+    }
+
+    /// Set what to do with cells wider than the `max_width`.
+    pub fn overflow(mut self, overflow: Overflow) -> Column {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn get_max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    pub fn get_min_width(&self) -> Option<usize> {
+        self.min_width
+    }
+
+    pub fn get_priority(&self) -> u32 {
+        self.priority
+    }
+
+    pub fn get_overflow(&self) -> Overflow {
+        self.overflow
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The lines drawn around and between the cells of a table.
+pub enum Border {
+    /// No border, columns are divided by whitespace.
+    ///
+    /// Separator lines (i.e. below the header) are still drawn using `─`.
+    #[default]
+    None,
+    /// `+`, `-` and `|`
+    Ascii,
+    /// `┌`, `─` and `│`
+    Light,
+    /// `┏`, `━` and `┃`
+    Heavy,
+    /// `╔`, `═` and `║`
+    Double,
+    /// `╭`, `─` and `│`
+    Rounded,
+}
+
+/// The characters used to draw a `Border`.
+pub(crate) struct Glyphs {
+    pub h: char,
+    pub v: char,
+    pub top: [char; 3],
+    pub mid: [char; 3],
+    pub bottom: [char; 3],
+}
+
+impl Border {
+    pub(crate) fn glyphs(&self) -> Glyphs {
+        let g = |h, v, top: &str, mid: &str, bottom: &str| {
+            let chars = |s: &str| {
+                let c: Vec<char> = s.chars().collect();
+                [c[0], c[1], c[2]]
+            };
+            Glyphs {
+                h,
+                v,
+                top: chars(top),
+                mid: chars(mid),
+                bottom: chars(bottom),
+            }
+        };
+        match *self {
+            Border::None => g('─', ' ', "   ", "   ", "   "),
+            Border::Ascii => g('-', '|', "+++", "+++", "+++"),
+            Border::Light => g('─', '│', "┌┬┐", "├┼┤", "└┴┘"),
+            Border::Heavy => g('━', '┃', "┏┳┓", "┣╋┫", "┗┻┛"),
+            Border::Double => g('═', '║', "╔╦╗", "╠╬╣", "╚╩╝"),
+            Border::Rounded => g('─', '│', "╭┬╮", "├┼┤", "╰┴╯"),
+        }
+    }
+}
+
+impl Table {
+    /// Create a new table from the given rows.
+    ///
+    /// The type can be thought of as `Rows[Cols[Cells[Text]]]`, where the items inside a `Cell`
+    /// will be concatenated together (alowing mixed formatting to exist within a table's cell).
+    pub fn new(table: Vec<Vec<Vec<Text>>>) -> Table {
+        Table {
+            table,
+            header: Vec::new(),
+            footer: Vec::new(),
+            header_style: Style::new().bold(),
+            footer_style: Style::new().dim(),
+            border: Border::None,
+            border_style: Style::new(),
+            separators: true,
+            columns: Vec::new(),
+            valign: VAlign::Top,
+            row_valign: BTreeMap::new(),
+            spans: BTreeMap::new(),
+            gap: 1,
+            padding: None,
+            indent: 0,
+            stripe: None,
+            cell_rules: Vec::new(),
+            hooks: StyleHooks::default(),
+        }
+    }
+
+    /// Add a header row, which is painted above the rows using the `header_style`.
+    ///
+    /// Can be called multiple times, i.e. to add a row of group headers which
+    /// [span](#method.header_span) several columns above the column names.
+    pub fn header(mut self, row: Vec<Vec<Text>>) -> Table {
+        self.header.push(row);
+        self
+    }
+
+    /// Add a footer row, which is painted below the rows using the `footer_style`.
+    ///
+    /// Can be called multiple times.
+    pub fn footer(mut self, row: Vec<Vec<Text>>) -> Table {
+        self.footer.push(row);
+        self
+    }
+
+    /// Set the style applied on top of the header's cells. Default is bold.
+    pub fn header_style(mut self, style: Style) -> Table {
+        self.header_style = style;
+        self
+    }
+
+    /// Set the style applied on top of the footer's cells. Default is dim.
+    pub fn footer_style(mut self, style: Style) -> Table {
+        self.footer_style = style;
+        self
+    }
+
+    /// Set the border drawn around and between the cells. Default is `Border::None`.
+    pub fn border(mut self, border: Border) -> Table {
+        self.border = border;
+        self
+    }
+
+    /// Set the style of the border and separator lines.
+    pub fn border_style(mut self, style: Style) -> Table {
+        self.border_style = style;
+        self
+    }
+
+    /// Whether to draw separator lines below the header and above the footer. Default is true.
+    pub fn separators(mut self, separators: bool) -> Table {
+        self.separators = separators;
+        self
+    }
+
+    /// Set the number of spaces between columns of a table without a border. Default is 1.
+    pub fn gap(mut self, gap: usize) -> Table {
+        self.gap = gap;
+        self
+    }
+
+    /// Set the number of spaces on the left and right of the text in each cell.
+    ///
+    /// Default is 0 for tables without a border and 1 for tables with a border.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let example = Table::new(vec![
+    ///     vec![cell("a"), cell("b")],
+    ///     vec![cell("c"), cell("d")],
+    /// ])
+    ///     .border(Border::Ascii)
+    ///     .padding(0)
+    ///     .indent(2);
+    ///
+    /// let expected = "  +-+-+
+    ///   |a|b|
+    ///   |c|d|
+    ///   +-+-+
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// example.paint(&mut result);
+    ///
+    /// assert_eq!(expected, String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn padding(mut self, padding: usize) -> Table {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Set the number of spaces to indent every line of the table with. Default is 0.
+    ///
+    /// Useful for nesting a table under i.e. an indented list item.
+    pub fn indent(mut self, indent: usize) -> Table {
+        self.indent = indent;
+        self
+    }
+
+    /// Apply the style to every other row (the 2nd, 4th, etc), i.e. a background color.
+    ///
+    /// Like all row styles, this also styles the padding between the cells of the row.
+    pub fn stripe(mut self, style: Style) -> Table {
+        self.stripe = Some(style);
+        self
+    }
+
+    /// Add a hook which decides the style of each row.
+    ///
+    /// The hook is called with the index and cells of every row (excluding the header and
+    /// footer). The returned style is applied on top of the cells' own styles and the stripe.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let table = Table::new(vec![
+    ///     vec![cell("foo.rs"), cell("12")],
+    ///     vec![cell("bar.rs"), cell("0")],
+    /// ])
+    ///     .row_style(|_, row| {
+    ///         if row[1][0].get_text() == "0" {
+    ///             Some(Style::new().dim())
+    ///         } else {
+    ///             None
+    ///         }
+    ///     });
+    /// # let _ = table;
+    /// # }
+    /// ```
+    pub fn row_style<F>(mut self, hook: F) -> Table
+    where
+        F: Fn(usize, &[Vec<Text>]) -> Option<Style> + Send + Sync + 'static,
+    {
+        self.hooks.rows.push(Arc::new(hook));
+        self
+    }
+
+    /// Add a hook which decides the style of each cell.
+    ///
+    /// The hook is called with the row index, the index of the cell within the row and the
+    /// cell's texts for every cell (excluding the header and footer). The returned style is
+    /// applied on top of the row's style.
+    pub fn cell_style<F>(mut self, hook: F) -> Table
+    where
+        F: Fn(usize, usize, &[Text]) -> Option<Style> + Send + Sync + 'static,
+    {
+        self.hooks.cells.push(Arc::new(hook));
+        self
+    }
+
+    /// Add a declarative rule styling the matching cells (or their rows).
+    ///
+    /// Rules are applied after the hooks.
+    pub fn cell_rule(mut self, rule: CellRule) -> Table {
+        self.cell_rules.push(rule);
+        self
+    }
+
+    /// Replace the cells of a column (excluding the header and footer) using `format`.
+    ///
+    /// `format` is called with the plain text of each cell and returns its new texts, allowing you
+    /// to i.e. style a column of numbers after loading the table with
+    /// [`from_csv`](#method.from_csv).
+    pub fn format_column<C, F>(mut self, column: C, format: F) -> Table
+    where
+        C: Into<ColumnRef>,
+        F: Fn(&str) -> Vec<Text>,
+    {
+        let index = match column.into().index(self.header.last()) {
+            Some(i) => i,
+            None => return self,
+        };
+        for row in &mut self.table {
+            if let Some(cell) = row.get_mut(index) {
+                *cell = format(&plain_text(cell));
+            }
+        }
+        self
+    }
+
+    /// Sort the rows (in ascending order) by the plain text of a column.
+    ///
+    /// The sort is stable, rows with equal cells keep their order. Rows missing the cell sort as
+    /// if it was empty. The spans and vertical alignment of the rows move with them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let table = Table::new(vec![
+    ///     vec![cell("file10"), cell("3")],
+    ///     vec![cell("file2"), cell("20")],
+    ///     vec![cell("file1"), cell("100")],
+    /// ]).header(vec![cell("name"), cell("size")])
+    ///     .sort("name", Sort::Natural)
+    ///     .select(vec!["size", "name"]);
+    ///
+    /// let mut result = Vec::new();
+    /// table.write_csv(&mut result).unwrap();
+    /// assert_eq!(
+    ///     "size,name\n100,file1\n20,file2\n3,file10\n",
+    ///     String::from_utf8(result).unwrap()
+    /// );
+    /// # }
+    /// ```
+    pub fn sort<C: Into<ColumnRef>>(self, column: C, sort: Sort) -> Table {
+        self.sort_rows(&column.into(), sort, false)
+    }
+
+    /// Sort the rows in descending order. See [`sort`](#method.sort).
+    pub fn sort_desc<C: Into<ColumnRef>>(self, column: C, sort: Sort) -> Table {
+        self.sort_rows(&column.into(), sort, true)
+    }
+
+    /// Keep only the rows (excluding the header and footer) for which `predicate` returns true.
+    pub fn filter<F>(mut self, predicate: F) -> Table
+    where
+        F: Fn(&[Vec<Text>]) -> bool,
+    {
+        let order: Vec<usize> = (0..self.table.len())
+            .filter(|&r| predicate(&self.table[r]))
+            .collect();
+        self.reorder_rows(&order);
+        self
+    }
+
+    /// Keep only the given columns, in the given order. Unknown columns are ignored.
+    ///
+    /// Columns are selected from every row, including the header and footer, together with their
+    /// settings, spans and (index based) cell rules. See [`sort`](#method.sort) for an example.
+    pub fn select<C, I>(mut self, columns: I) -> Table
+    where
+        C: Into<ColumnRef>,
+        I: IntoIterator<Item = C>,
+    {
+        let order: Vec<usize> = {
+            let names = self.header.last();
+            columns
+                .into_iter()
+                .filter_map(|c| c.into().index(names))
+                .collect()
+        };
+        let pick = |rows: Vec<Vec<Vec<Text>>>| -> Vec<Vec<Vec<Text>>> {
+            rows.into_iter()
+                .map(|row| {
+                    order
+                        .iter()
+                        .map(|&i| row.get(i).cloned().unwrap_or_default())
+                        .collect()
+                })
+                .collect()
+        };
+        self.header = pick(mem::take(&mut self.header));
+        self.table = pick(mem::take(&mut self.table));
+        self.footer = pick(mem::take(&mut self.footer));
+        self.columns = order.iter().map(|&i| self.get_column(i)).collect();
+
+        let new_index = |i: usize| order.iter().position(|&o| o == i);
+        self.spans = mem::take(&mut self.spans)
+            .into_iter()
+            .filter_map(|((part, r, c), span)| new_index(c).map(|c| ((part, r, c), span)))
+            .collect();
+        self.cell_rules = mem::take(&mut self.cell_rules)
+            .into_iter()
+            .filter_map(|mut rule| {
+                if let ColumnRef::Index(i) = rule.column {
+                    rule.column = ColumnRef::Index(new_index(i)?);
+                }
+                Some(rule)
+            })
+            .collect();
+        self
+    }
+
+    fn sort_rows(mut self, column: &ColumnRef, sort: Sort, descending: bool) -> Table {
+        let index = match column.index(self.header.last()) {
+            Some(i) => i,
+            None => return self,
+        };
+        let keys: Vec<String> = self.table
+            .iter()
+            .map(|row| row.get(index).map(|c| plain_text(c)).unwrap_or_default())
+            .collect();
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|&a, &b| {
+            let order = sort.compare(&keys[a], &keys[b]);
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+        self.reorder_rows(&order);
+        self
+    }
+
+    /// Keep the rows at the indexes in `order`, in that order.
+    fn reorder_rows(&mut self, order: &[usize]) {
+        let new_index = |r: usize| order.iter().position(|&o| o == r);
+        let mut rows: Vec<Option<Vec<Vec<Text>>>> =
+            mem::take(&mut self.table).into_iter().map(Some).collect();
+        self.table = order
+            .iter()
+            .map(|&r| rows[r].take().expect("rows are only used once"))
+            .collect();
+        self.spans = mem::take(&mut self.spans)
+            .into_iter()
+            .filter_map(|((part, r, c), span)| match part {
+                Part::Body => new_index(r).map(|r| ((part, r, c), span)),
+                _ => Some(((part, r, c), span)),
+            })
+            .collect();
+        self.row_valign = mem::take(&mut self.row_valign)
+            .into_iter()
+            .filter_map(|(r, valign)| new_index(r).map(|r| (r, valign)))
+            .collect();
+    }
+
+    /// Set the settings of the column at `index`.
+    pub fn column(mut self, index: usize, column: Column) -> Table {
+        if self.columns.len() <= index {
+            self.columns.resize(index + 1, Column::default());
+        }
+        self.columns[index] = column;
+        self
+    }
+
+    /// Set the settings of all columns, starting from the first one.
+    pub fn columns(mut self, columns: Vec<Column>) -> Table {
+        self.columns = columns;
+        self
+    }
+
+    /// Set the vertical alignment of the cells in all rows. Default is `VAlign::Top`.
+    pub fn valign(mut self, valign: VAlign) -> Table {
+        self.valign = valign;
+        self
+    }
+
+    /// Set the vertical alignment of the cells in a single row, overriding `valign`.
+    ///
+    /// `row` is the index into the rows, excluding the header.
+    pub fn row_valign(mut self, row: usize, valign: VAlign) -> Table {
+        self.row_valign.insert(row, valign);
+        self
+    }
+
+    /// Make a cell in the rows span multiple columns and/or rows.
+    ///
+    /// `row` is the index into the rows (excluding the header) and `cell` is the index of the cell
+    /// within that row. As in html, the cells after a spanning cell are shifted to the right and
+    /// the cells in the rows below skip the columns it covers.
+    ///
+    /// Row spans never extend past the last row of the body.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let mut example = Table::new(vec![
+    ///     vec![cell("GET /"), cell("1ms"), cell("10ms")],
+    ///     vec![cell("POST /"), cell("2ms"), cell("20ms")],
+    /// ])
+    ///     .header(vec![cell(""), cell("Latency")])
+    ///     .header(vec![cell("Route"), cell("p50"), cell("p99")])
+    ///     .header_span(0, 1, 2, 1)
+    ///     .border(Border::Ascii);
+    ///
+    /// example.set_plain();
+    /// let expected = "\
+    /// +--------+------------+
+    /// |        | Latency    |
+    /// | Route  | p50 | p99  |
+    /// +--------+-----+------+
+    /// | GET /  | 1ms | 10ms |
+    /// | POST / | 2ms | 20ms |
+    /// +--------+-----+------+
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// example.paint(&mut result);
+    ///
+    /// assert_eq!(expected, String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn span(self, row: usize, cell: usize, colspan: usize, rowspan: usize) -> Table {
+        self.set_span(Part::Body, row, cell, colspan, rowspan)
+    }
+
+    /// Make a cell in the header rows span multiple columns and/or rows.
+    ///
+    /// See [`span`](#method.span).
+    pub fn header_span(self, row: usize, cell: usize, colspan: usize, rowspan: usize) -> Table {
+        self.set_span(Part::Header, row, cell, colspan, rowspan)
+    }
+
+    /// Make a cell in the footer rows span multiple columns and/or rows.
+    ///
+    /// See [`span`](#method.span).
+    pub fn footer_span(self, row: usize, cell: usize, colspan: usize, rowspan: usize) -> Table {
+        self.set_span(Part::Footer, row, cell, colspan, rowspan)
+    }
+
+    fn set_span(
+        mut self,
+        part: Part,
+        row: usize,
+        cell: usize,
+        colspan: usize,
+        rowspan: usize,
+    ) -> Table {
+        let span = (cmp::max(colspan, 1), cmp::max(rowspan, 1));
+        self.spans.insert((part, row, cell), span);
+        self
+    }
+
+    /// The rows of the table, excluding the header and footer.
+    pub fn rows(&self) -> &[Vec<Vec<Text>>] {
+        &self.table
+    }
+
+    /// The header rows of the table, empty if it has no header.
+    pub fn get_header(&self) -> &[Vec<Vec<Text>>] {
+        &self.header
+    }
+
+    /// The footer rows of the table, empty if it has no footer.
+    pub fn get_footer(&self) -> &[Vec<Vec<Text>>] {
+        &self.footer
+    }
+
+    /// The `(colspan, rowspan)` of a cell in the rows. `(1, 1)` unless set with `span`.
+    pub fn get_span(&self, row: usize, cell: usize) -> (usize, usize) {
+        self.spans
+            .get(&(Part::Body, row, cell))
+            .cloned()
+            .unwrap_or((1, 1))
+    }
+
+    pub fn get_border(&self) -> Border {
+        self.border
+    }
+
+    pub fn get_gap(&self) -> usize {
+        self.gap
+    }
+
+    /// The padding of the cells, taking the default for the border into account.
+    pub fn get_padding(&self) -> usize {
+        match self.padding {
+            Some(p) => p,
+            None if self.is_boxed() => 1,
+            None => 0,
+        }
+    }
+
+    pub fn get_indent(&self) -> usize {
+        self.indent
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        let rows = self.table
+            .iter_mut()
+            .chain(&mut self.header)
+            .chain(&mut self.footer);
+        for row in rows {
+            for col in row {
+                for t in col {
+                    t.set_plain();
+                }
+            }
+        }
+        self.header_style = Style::new();
+        self.footer_style = Style::new();
+        self.border_style = Style::new();
+        self.stripe = None;
+        self.cell_rules.clear();
+        self.hooks = StyleHooks::default();
+    }
+
+    /// Paint the table, giving each column the same width.
+    pub fn paint<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        layout::paint_lines(w, &self.lines(None))
+    }
+
+    /// Paint the table, shrinking it to fit within `width` columns.
+    ///
+    /// If the table is too wide then the widest columns with the lowest
+    /// [`priority`](struct.Column.html#method.priority) are shrunk first, until the table fits or
+    /// all columns are at their `min_width`. Cells which no longer fit in their column are wrapped
+    /// or truncated according to the column's [`Overflow`](enum.Overflow.html).
+    ///
+    /// Use [`term_width`](fn.term_width.html) to fit the table to the terminal.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let cell = |s: &str| vec![Text::new(s.into())];
+    /// let example = Table::new(vec![
+    ///     vec![cell("important"), cell("some long description")],
+    /// ])
+    ///     .column(0, Column::new().priority(1));
+    ///
+    /// let expected = "\
+    /// important some long
+    ///           description
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// example.paint_width(&mut result, 21);
+    ///
+    /// assert_eq!(expected.as_bytes(), result.as_slice());
+    /// # }
+    /// ```
+    pub fn paint_width<W: io::Write>(&self, w: &mut W, width: usize) -> io::Result<()> {
+        layout::paint_lines(w, &self.lines(Some(width)))
+    }
+
+    /// Render the table into lines, fitting them in `width` if it is given.
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let header = self.styled_header();
+        let footer = self.styled_footer();
+        let (body, fills) = self.styled_body(0, &self.table);
+        let mut body = self.grid(Part::Body, &body, 0);
+        body.fills = fills;
+        let mut grids = vec![
+            self.grid(Part::Header, &header, 0),
+            body,
+            self.grid(Part::Footer, &footer, 0),
+        ];
+        grids.retain(|g| !g.slots.is_empty());
+        let widths = self.widths(&mut grids, width);
+        let ncols = widths.len();
+
+        let mut out = Vec::new();
+        let no_bounds = vec![false; ncols];
+        for (i, grid) in grids.iter().enumerate() {
+            if i == 0 && self.is_boxed() {
+                out.push(self.rule_line(&widths, 0, &no_bounds, &grid.bounds(0, ncols)));
+            } else if i > 0 && self.separators {
+                let above = grids[i - 1].bounds(grids[i - 1].slots.len() - 1, ncols);
+                out.push(self.rule_line(&widths, 1, &above, &grid.bounds(0, ncols)));
+            }
+            self.grid_lines(&widths, grid, &mut out);
+        }
+        if let (Some(last), true) = (grids.last(), self.is_boxed()) {
+            let above = last.bounds(last.slots.len() - 1, ncols);
+            out.push(self.rule_line(&widths, 2, &above, &no_bounds));
+        }
+        out
+    }
+
+    /// Remove the rows of the body, leaving the header, footer and settings.
+    pub(crate) fn take_rows(&mut self) -> Vec<Vec<Vec<Text>>> {
+        mem::take(&mut self.table)
+    }
+
+    /// The width of each column needed to fit the header, footer and `rows` (the body rows
+    /// starting at row `first`), limited by the columns' `max_width`.
+    pub(crate) fn measure(&self, first: usize, rows: &[Vec<Vec<Text>>]) -> Vec<usize> {
+        let mut grids = vec![
+            self.grid(Part::Header, &self.header, 0),
+            self.grid(Part::Body, rows, first),
+            self.grid(Part::Footer, &self.footer, 0),
+        ];
+        self.widths(&mut grids, None)
+    }
+
+    /// The number of `rows` (the body rows starting at row `first`) which can be laid out without
+    /// cutting a row span short, i.e. the rows before the first span reaching past the last row.
+    pub(crate) fn unspanned_rows(&self, first: usize, rows: &[Vec<Vec<Text>>]) -> usize {
+        let mut end = rows.len();
+        for (r, row) in rows.iter().enumerate() {
+            for c in 0..row.len() {
+                let (_, rowspan) = self.get_span(first + r, c);
+                if r + rowspan > rows.len() {
+                    end = cmp::min(end, r);
+                }
+            }
+        }
+        end
+    }
+
+    /// The lines above the body when streaming the table with the given column widths: the top
+    /// border, the header and the separator below it.
+    pub(crate) fn head_lines(&self, widths: &[usize]) -> Vec<Line> {
+        let all_bounds = vec![true; widths.len()];
+        let mut grid = self.grid(Part::Header, &self.styled_header(), 0);
+        self.layout_grid(&mut grid, widths);
+        let mut out = Vec::new();
+        let last = match grid.slots.len() {
+            0 => None,
+            n => Some(grid.bounds(n - 1, widths.len())),
+        };
+        if self.is_boxed() {
+            let below = match last {
+                Some(_) => grid.bounds(0, widths.len()),
+                None => all_bounds.clone(),
+            };
+            out.push(self.rule_line(widths, 0, &vec![false; widths.len()], &below));
+        }
+        self.grid_lines(widths, &grid, &mut out);
+        if let (Some(above), true) = (last, self.separators) {
+            out.push(self.rule_line(widths, 1, &above, &all_bounds));
+        }
+        out
+    }
+
+    /// The lines of `rows` (the body rows starting at row `first`) with the given column widths.
+    pub(crate) fn body_lines(
+        &self,
+        widths: &[usize],
+        first: usize,
+        rows: &[Vec<Vec<Text>>],
+    ) -> Vec<Line> {
+        let (body, fills) = self.styled_body(first, rows);
+        let mut grid = self.grid(Part::Body, &body, first);
+        grid.fills = fills;
+        self.layout_grid(&mut grid, widths);
+        let mut out = Vec::new();
+        self.grid_lines(widths, &grid, &mut out);
+        out
+    }
+
+    /// The lines below the body when streaming the table with the given column widths: the
+    /// separator above the footer, the footer and the bottom border.
+    pub(crate) fn foot_lines(&self, widths: &[usize]) -> Vec<Line> {
+        let all_bounds = vec![true; widths.len()];
+        let mut grid = self.grid(Part::Footer, &self.styled_footer(), 0);
+        self.layout_grid(&mut grid, widths);
+        let mut out = Vec::new();
+        let rows = grid.slots.len();
+        if rows > 0 && self.separators {
+            out.push(self.rule_line(widths, 1, &all_bounds, &grid.bounds(0, widths.len())));
+        }
+        self.grid_lines(widths, &grid, &mut out);
+        if self.is_boxed() {
+            let above = match rows {
+                0 => all_bounds,
+                n => grid.bounds(n - 1, widths.len()),
+            };
+            out.push(self.rule_line(widths, 2, &above, &vec![false; widths.len()]));
+        }
+        out
+    }
+
+    fn styled_header(&self) -> Vec<Vec<Vec<Text>>> {
+        self.header
+            .iter()
+            .map(|r| styled_row(r, &self.header_style))
+            .collect()
+    }
+
+    fn styled_footer(&self) -> Vec<Vec<Vec<Text>>> {
+        self.footer
+            .iter()
+            .map(|r| styled_row(r, &self.footer_style))
+            .collect()
+    }
+
+    /// Lay out the grids (fitting them in `width`) and return the width of each column.
+    fn widths(&self, grids: &mut [Grid], width: Option<usize>) -> Vec<usize> {
+        let ncols = grids.iter().map(|g| g.ncols).max().unwrap_or(0);
+
+        // The widest each column is allowed to be.
+        let mut limits = self.col_widths(grids, ncols, |cell| {
+            layout::split_lines(&cell.texts)
+                .iter()
+                .map(|l| layout::line_width(l))
+                .max()
+                .unwrap_or(0)
+        });
+        for (i, limit) in limits.iter_mut().enumerate() {
+            if let Some(max) = self.get_column(i).max_width {
+                *limit = cmp::min(*limit, cmp::max(max, 1));
+            }
+        }
+        if let Some(width) = width {
+            self.fit(&mut limits, width);
+        }
+
+        for grid in grids.iter_mut() {
+            self.layout_grid(grid, &limits);
+        }
+        self.col_widths(grids, ncols, |cell| {
+            cell.lines
+                .iter()
+                .map(|l| layout::line_width(l))
+                .max()
+                .unwrap_or(0)
+        })
+    }
+
+    /// Lay out the cells of the grid in the column widths and make its rows tall enough.
+    fn layout_grid(&self, grid: &mut Grid, widths: &[usize]) {
+        for cell in &mut grid.cells {
+            let width = self.span_width(widths, cell.col, cell.colspan);
+            cell.lines = self.cell_lines(cell.col, &cell.texts, width);
+        }
+        grid.fit_heights();
+    }
+
+    /// Push the lines of every row of the grid.
+    fn grid_lines(&self, widths: &[usize], grid: &Grid, out: &mut Vec<Line>) {
+        for row in 0..grid.slots.len() {
+            for n in 0..grid.heights[row] {
+                out.push(self.row_line(widths, grid, row, n));
+            }
+        }
+    }
+
+    /// The rows (starting at row `first`) with the stripe, hooks and rules applied, along with
+    /// the style of each row.
+    fn styled_body(
+        &self,
+        first: usize,
+        rows: &[Vec<Vec<Text>>],
+    ) -> (Vec<Vec<Vec<Text>>>, Vec<Style>) {
+        let header = self.header.last();
+        let rules: Vec<(usize, &CellRule)> = self.cell_rules
+            .iter()
+            .filter_map(|rule| rule.column.index(header).map(|i| (i, rule)))
+            .collect();
+
+        let mut styled_rows = Vec::new();
+        let mut fills = Vec::new();
+        for (r, row) in rows.iter().enumerate() {
+            let r = first + r;
+            let mut fill = Style::new();
+            if let (1, Some(ref stripe)) = (r % 2, self.stripe) {
+                fill = fill.with_style(stripe);
+            }
+            for hook in &self.hooks.rows {
+                if let Some(style) = hook(r, row) {
+                    fill = fill.with_style(&style);
+                }
+            }
+            for &(c, rule) in &rules {
+                if rule.row && row.get(c).map(|cell| rule.matches(cell)).unwrap_or(false) {
+                    fill = fill.with_style(&rule.style);
+                }
+            }
+
+            let styled = row.iter()
+                .enumerate()
+                .map(|(c, cell)| {
+                    let mut style = fill;
+                    for hook in &self.hooks.cells {
+                        if let Some(s) = hook(r, c, cell) {
+                            style = style.with_style(&s);
+                        }
+                    }
+                    for &(i, rule) in &rules {
+                        if !rule.row && i == c && rule.matches(cell) {
+                            style = style.with_style(&rule.style);
+                        }
+                    }
+                    cell.iter().map(|t| t.clone().with_style(&style)).collect()
+                })
+                .collect();
+            styled_rows.push(styled);
+            fills.push(fill);
+        }
+        (styled_rows, fills)
+    }
+
+    /// Place the cells of the rows into a grid, according to their spans.
+    ///
+    /// `first` is the index of the first row, i.e. when streaming.
+    fn grid(&self, part: Part, rows: &[Vec<Vec<Text>>], first: usize) -> Grid {
+        let mut slots: Vec<Vec<Option<usize>>> = vec![Vec::new(); rows.len()];
+        let mut cells = Vec::new();
+        for (r, row) in rows.iter().enumerate() {
+            let mut col = 0;
+            for (c, texts) in row.iter().enumerate() {
+                while slots[r].get(col).map(Option::is_some).unwrap_or(false) {
+                    col += 1;
+                }
+                let (colspan, rowspan) = self.spans
+                    .get(&(part, first + r, c))
+                    .cloned()
+                    .unwrap_or((1, 1));
+                let rowspan = cmp::min(rowspan, rows.len() - r);
+                for row_slots in &mut slots[r..r + rowspan] {
+                    if row_slots.len() < col + colspan {
+                        row_slots.resize(col + colspan, None);
+                    }
+                    for slot in &mut row_slots[col..col + colspan] {
+                        *slot = Some(cells.len());
+                    }
+                }
+                let valign = match part {
+                    Part::Body => self.row_valign
+                        .get(&(first + r))
+                        .cloned()
+                        .unwrap_or(self.valign),
+                    _ => self.valign,
+                };
+                cells.push(Cell {
+                    row: r,
+                    col,
+                    colspan,
+                    rowspan,
+                    valign,
+                    texts: texts.clone(),
+                    lines: Vec::new(),
+                });
+                col += colspan;
+            }
+        }
+        let ncols = slots.iter().map(Vec::len).max().unwrap_or(0);
+        Grid {
+            heights: vec![1; slots.len()],
+            fills: vec![Style::new(); slots.len()],
+            slots,
+            cells,
+            ncols,
+        }
+    }
+
+    /// The plain text of the column names (the last header row), of the body rows and of the
+    /// footer rows, with one entry per column.
+    ///
+    /// The text of a cell spanning multiple columns or rows is only put in its top left slot.
+    pub(crate) fn plain_grid(&self) -> PlainGrid {
+        let header = self.grid(Part::Header, &self.header, 0);
+        let body = self.grid(Part::Body, &self.table, 0);
+        let footer = self.grid(Part::Footer, &self.footer, 0);
+        let ncols = cmp::max(cmp::max(header.ncols, body.ncols), footer.ncols);
+        PlainGrid {
+            names: header.plain_rows(ncols).pop(),
+            rows: body.plain_rows(ncols),
+            footer: footer.plain_rows(ncols),
+        }
+    }
+
+    /// The width of each column needed to fit the cells, as measured by `measure`.
+    ///
+    /// Cells spanning multiple columns widen the columns they span (evenly) if they don't fit.
+    fn col_widths<F>(&self, grids: &[Grid], ncols: usize, measure: F) -> Vec<usize>
+    where
+        F: Fn(&Cell) -> usize,
+    {
+        let mut widths = vec![0; ncols];
+        let mut cells: Vec<&Cell> = grids.iter().flat_map(|g| g.cells.iter()).collect();
+        cells.sort_by_key(|c| c.colspan);
+        for cell in cells {
+            let needed = measure(cell);
+            let have = self.span_width(&widths, cell.col, cell.colspan);
+            if needed <= have {
+                continue;
+            }
+            let extra = needed - have;
+            for i in 0..cell.colspan {
+                let share = extra / cell.colspan + if i < extra % cell.colspan { 1 } else { 0 };
+                widths[cell.col + i] += share;
+            }
+        }
+        widths
+    }
+
+    /// The width of `colspan` columns starting at `col`, including the dividers between them.
+    fn span_width(&self, widths: &[usize], col: usize, colspan: usize) -> usize {
+        let divider = self.divider() + 2 * self.get_padding();
+        widths[col..col + colspan].iter().sum::<usize>() + divider * (colspan - 1)
+    }
+
+    /// The width of the divider between columns, excluding the padding.
+    fn divider(&self) -> usize {
+        if self.is_boxed() {
+            1
+        } else {
+            self.gap
+        }
+    }
+
+    /// The total width of the table, given the widths of its columns.
+    fn total_width(&self, widths: &[usize]) -> usize {
+        let n = widths.len();
+        let edges = if self.is_boxed() { 2 } else { 0 };
+        self.indent + edges + widths.iter().sum::<usize>() + 2 * self.get_padding() * n
+            + self.divider() * n.saturating_sub(1)
+    }
+
+    /// Shrink the column widths until the table is at most `width` wide.
+    fn fit(&self, widths: &mut [usize], width: usize) {
+        let n = widths.len();
+        let mut excess = self.total_width(widths).saturating_sub(width);
+
+        let mins: Vec<usize> = (0..n)
+            .map(|i| cmp::max(self.get_column(i).min_width.unwrap_or(1), 1))
+            .collect();
+        let mut priorities: Vec<u32> = (0..n).map(|i| self.get_column(i).priority).collect();
+        priorities.sort();
+        priorities.dedup();
+
+        for priority in priorities {
+            while excess > 0 {
+                // shrink the widest column of this priority which can still be shrunk
+                let widest = (0..n)
+                    .filter(|&i| self.get_column(i).priority == priority && widths[i] > mins[i])
+                    .max_by_key(|&i| (widths[i], cmp::Reverse(i)));
+                match widest {
+                    Some(i) => {
+                        widths[i] -= 1;
+                        excess -= 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    fn get_column(&self, index: usize) -> Column {
+        self.columns.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Lay out the cell so that it fits in `width`, according to the column's overflow policy.
+    fn cell_lines(&self, index: usize, cell: &[Text], width: usize) -> Vec<Line> {
+        let overflow = self.get_column(index).overflow;
+        let mut out = Vec::new();
+        for line in layout::split_lines(cell) {
+            if layout::line_width(&line) <= width {
+                out.push(line);
+                continue;
+            }
+            match overflow {
+                Overflow::Wrap => out.extend(layout::wrap_line(&line, width)),
+                Overflow::Truncate => out.push(layout::truncate_end(&line, width)),
+                Overflow::TruncateMiddle => out.push(layout::truncate_middle(&line, width)),
+            }
+        }
+        out
+    }
+
+    fn is_boxed(&self) -> bool {
+        self.border != Border::None
+    }
+
+    fn glyph(&self, s: String) -> Text {
+        Text::new(s).with_style(&self.border_style)
+    }
+
+    /// A horizontal line. `kind` is 0 for the top, 1 for the middle and 2 for the bottom.
+    ///
+    /// `above` and `below` are whether the rows above and below the line have a divider after
+    /// each column, which decides the junctions to draw.
+    fn rule_line(&self, widths: &[usize], kind: usize, above: &[bool], below: &[bool]) -> Line {
+        let glyphs = self.border.glyphs();
+        let indent = Text::new(repeat(' ', self.indent));
+        if !self.is_boxed() {
+            let total = self.total_width(widths) - self.indent;
+            return vec![indent, self.glyph(repeat(glyphs.h, total))];
+        }
+        let corners = match kind {
+            0 => glyphs.top,
+            1 => glyphs.mid,
+            _ => glyphs.bottom,
+        };
+        let mut s = String::new();
+        s.push(corners[0]);
+        for (i, w) in widths.iter().enumerate() {
+            if i > 0 {
+                s.push(match (above[i - 1], below[i - 1]) {
+                    (true, true) => glyphs.mid[1],
+                    (true, false) => glyphs.bottom[1],
+                    (false, true) => glyphs.top[1],
+                    (false, false) => glyphs.h,
+                });
+            }
+            s.push_str(&repeat(glyphs.h, w + 2 * self.get_padding()));
+        }
+        s.push(corners[2]);
+        vec![indent, self.glyph(s)]
+    }
+
+    /// The `n`th line of a row in the grid.
+    fn row_line(&self, widths: &[usize], grid: &Grid, row: usize, n: usize) -> Line {
+        // The segments of the line: each is either a cell or an empty column.
+        let mut segments: Vec<(&[Text], usize)> = Vec::new();
+        let mut col = 0;
+        while col < widths.len() {
+            match grid.slots[row].get(col).cloned().unwrap_or(None) {
+                Some(i) => {
+                    let cell = &grid.cells[i];
+                    let line = grid.cell_line(cell, row, n);
+                    segments.push((line, self.span_width(widths, col, cell.colspan)));
+                    col += cell.colspan;
+                }
+                None => {
+                    segments.push((&[], widths[col]));
+                    col += 1;
+                }
+            }
+        }
+
+        let padding = self.get_padding();
+        let fill = &grid.fills[row];
+        let mut line = Vec::new();
+        if !self.is_boxed() {
+            // don't pad the cells after the last one with any text
+            let last = match segments.iter().rposition(|s| !s.0.is_empty()) {
+                Some(last) => last,
+                None => return line,
+            };
+            let mut end = self.indent;
+            layout::pad_line(&mut line, end);
+            for (i, &(texts, width)) in segments.iter().enumerate().take(last + 1) {
+                layout::pad_line_styled(&mut line, end + padding, fill);
+                line.extend(texts.iter().cloned());
+                end += width + 2 * padding + self.gap;
+                if i < last {
+                    layout::pad_line_styled(&mut line, end, fill);
+                }
+            }
+            return line;
+        }
+
+        let v = self.glyph(self.border.glyphs().v.to_string());
+        let mut end = self.indent;
+        layout::pad_line(&mut line, end);
+        for &(texts, width) in &segments {
+            line.push(v.clone());
+            end += 1;
+            layout::pad_line_styled(&mut line, end + padding, fill);
+            line.extend(texts.iter().cloned());
+            end += width + 2 * padding;
+            layout::pad_line_styled(&mut line, end, fill);
+        }
+        line.push(v);
+        line
+    }
+}
+
+/// The plain text of a table's cells, as returned by `Table::plain_grid`.
+pub(crate) struct PlainGrid {
+    /// The column names (the last header row), if the table has a header.
+    pub names: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+    pub footer: Vec<Vec<String>>,
+}
+
+/// The cells of a part of the table, placed on a grid according to their spans.
+struct Grid {
+    /// For each row, the index of the cell covering each column.
+    slots: Vec<Vec<Option<usize>>>,
+    cells: Vec<Cell>,
+    /// The height of each row, in lines.
+    heights: Vec<usize>,
+    /// The style of the padding in each row.
+    fills: Vec<Style>,
+    ncols: usize,
+}
+
+/// A cell placed on a `Grid`.
+struct Cell {
+    /// The top left corner of the cell.
+    row: usize,
+    col: usize,
+    colspan: usize,
+    rowspan: usize,
+    valign: VAlign,
+    texts: Vec<Text>,
+    /// The laid out lines of the cell.
+    lines: Vec<Line>,
+}
+
+impl Grid {
+    /// Make each row tall enough for its cells.
+    fn fit_heights(&mut self) {
+        let mut cells: Vec<&Cell> = self.cells.iter().collect();
+        cells.sort_by_key(|c| c.rowspan);
+        for cell in cells {
+            let rows = cell.row..cell.row + cell.rowspan;
+            let have: usize = self.heights[rows].iter().sum();
+            if cell.lines.len() > have {
+                self.heights[cell.row + cell.rowspan - 1] += cell.lines.len() - have;
+            }
+        }
+    }
+
+    /// The `n`th line of `cell` within `row`.
+    fn cell_line<'a>(&self, cell: &'a Cell, row: usize, n: usize) -> &'a [Text] {
+        let height: usize = self.heights[cell.row..cell.row + cell.rowspan].iter().sum();
+        let pad = height - cell.lines.len();
+        let top = match cell.valign {
+            VAlign::Top => 0,
+            VAlign::Middle => pad / 2,
+            VAlign::Bottom => pad,
+        };
+        let y = self.heights[cell.row..row].iter().sum::<usize>() + n;
+        if y < top {
+            return &[];
+        }
+        cell.lines.get(y - top).map(|l| &l[..]).unwrap_or(&[])
+    }
+
+    /// The plain text of each of the `ncols` slots of every row.
+    fn plain_rows(&self, ncols: usize) -> Vec<Vec<String>> {
+        (0..self.slots.len())
+            .map(|r| {
+                (0..ncols)
+                    .map(|c| match self.slots[r].get(c).cloned().unwrap_or(None) {
+                        Some(i) if self.cells[i].row == r && self.cells[i].col == c => {
+                            plain_text(&self.cells[i].texts)
+                        }
+                        _ => String::new(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Whether there is a divider after each of the `ncols` columns in the row.
+    fn bounds(&self, row: usize, ncols: usize) -> Vec<bool> {
+        let slots = &self.slots[row];
+        let slot = |c: usize| slots.get(c).cloned().unwrap_or(None);
+        (0..ncols)
+            .map(|c| slot(c).is_none() || slot(c) != slot(c + 1))
+            .collect()
+    }
+}
+
+/// The text of the cell without any style.
+fn plain_text(cell: &[Text]) -> String {
+    cell.iter().map(|t| t.get_text()).collect()
+}
+
+fn styled_row(row: &[Vec<Text>], style: &Style) -> Vec<Vec<Text>> {
+    row.iter()
+        .map(|cell| cell.iter().map(|t| t.clone().with_style(style)).collect())
+        .collect()
+}
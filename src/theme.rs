@@ -0,0 +1,136 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Semantic roles of texts and the themes which style them.
+
+use std::io;
+use std_prelude::*;
+
+use {Color, El, Style};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// What a text means, styled by the [`Theme`](struct.Theme.html) it is painted with.
+pub enum Role {
+    Error,
+    Warning,
+    Success,
+    Muted,
+    Emphasis,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// The style of each [`Role`](enum.Role.html), applied when painting.
+///
+/// The style of the role is applied first, the text's own attributes on top of it. Themes use the
+/// names of the roles in lowercase when deserialized (i.e. from a yaml or toml file), missing
+/// roles keep their style from the dark theme.
+///
+/// # Examples
+/// ```rust
+/// # extern crate serde_yaml;
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let els = vec![
+///     El::Text(Text::new("failed".into()).role(Role::Error)),
+///     El::plain("\n".into()),
+/// ];
+/// let theme: Theme = serde_yaml::from_str("error: {c: purple}").unwrap();
+///
+/// let mut result = Vec::new();
+/// theme.paint(&mut result, &els).unwrap();
+/// # #[cfg(unix)]
+/// assert_eq!("\x1b[35mfailed\x1b[0m\n", String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Theme {
+    error: Style,
+    warning: Style,
+    success: Style,
+    muted: Style,
+    emphasis: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// The default theme, for terminals with a dark background.
+    pub fn dark() -> Theme {
+        Theme {
+            error: Style::new().bold().color(Color::Red),
+            warning: Style::new().bold().color(Color::Yellow),
+            success: Style::new().color(Color::Green),
+            muted: Style::new().dim(),
+            emphasis: Style::new().bold(),
+        }
+    }
+
+    /// A theme for terminals with a light background, avoiding yellow.
+    pub fn light() -> Theme {
+        Theme {
+            warning: Style::new().bold().color(Color::Purple),
+            ..Theme::dark()
+        }
+    }
+
+    /// A theme with background colors and without dim text.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            error: Style::new().bold().color(Color::White).bg(Color::Red),
+            warning: Style::new().bold().color(Color::Black).bg(Color::Yellow),
+            success: Style::new().bold().color(Color::Black).bg(Color::Green),
+            muted: Style::new(),
+            emphasis: Style::new().bold().color(Color::White),
+        }
+    }
+
+    /// Set the style of the role.
+    pub fn role(mut self, role: Role, style: Style) -> Theme {
+        match role {
+            Role::Error => self.error = style,
+            Role::Warning => self.warning = style,
+            Role::Success => self.success = style,
+            Role::Muted => self.muted = style,
+            Role::Emphasis => self.emphasis = style,
+        }
+        self
+    }
+
+    pub fn get(&self, role: Role) -> Style {
+        match role {
+            Role::Error => self.error,
+            Role::Warning => self.warning,
+            Role::Success => self.success,
+            Role::Muted => self.muted,
+            Role::Emphasis => self.emphasis,
+        }
+    }
+
+    /// Paint the elements like [`paint`](fn.paint.html), styling their roles with the theme.
+    pub fn paint<W: io::Write>(&self, w: &mut W, items: &[El]) -> io::Result<()> {
+        for item in items {
+            item.paint_with(w, None, self)?;
+        }
+        Ok(())
+    }
+
+    /// Paint the elements like [`paint_width`](fn.paint_width.html), styling their roles with the
+    /// theme.
+    pub fn paint_width<W: io::Write>(&self, w: &mut W, items: &[El], width: usize) -> io::Result<()> {
+        for item in items {
+            item.paint_with(w, Some(width), self)?;
+        }
+        Ok(())
+    }
+}
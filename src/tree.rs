@@ -0,0 +1,219 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Trees of labels, i.e. dependency trees and directory listings.
+
+use std_prelude::*;
+
+use block::{inline_lines, DEFAULT_WIDTH};
+use layout::{self, Line};
+use {Style, Text};
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// A node of a [`Tree`](struct.Tree.html): a label and its children.
+pub struct Node {
+    label: Vec<Text>,
+    children: Vec<Node>,
+    collapsed: bool,
+}
+
+impl Node {
+    pub fn new(label: Vec<Text>) -> Node {
+        Node {
+            label,
+            ..Node::default()
+        }
+    }
+
+    /// Add a child below the others.
+    pub fn child(mut self, child: Node) -> Node {
+        self.children.push(child);
+        self
+    }
+
+    /// Set the children of the node.
+    pub fn children(mut self, children: Vec<Node>) -> Node {
+        self.children = children;
+        self
+    }
+
+    /// Hide the children of the node, marking it with `…` (or `...` in ascii) instead.
+    pub fn collapsed(mut self, collapsed: bool) -> Node {
+        self.collapsed = collapsed;
+        self
+    }
+
+    pub fn get_label(&self) -> &[Text] {
+        &self.label
+    }
+
+    pub fn get_children(&self) -> &[Node] {
+        &self.children
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        for t in &mut self.label {
+            t.set_plain();
+        }
+        for child in &mut self.children {
+            child.set_plain();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+/// A tree drawn with `├──`, `└──` and `│` guides (or `|--`, `` `-- `` and `|` in ascii).
+///
+/// The roots are drawn without guides. Labels can contain multiple lines and are wrapped to the
+/// width, their lines after the first are lined up with the first.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let node = |s: &str| Node::new(vec![Text::new(s.into())]);
+/// let tree = Tree::new(vec![
+///     node("termstyle").children(vec![
+///         node("ansi_term"),
+///         node("serde").child(node("serde_derive")),
+///         node("csv").child(node("csv-core")).collapsed(true),
+///     ]),
+/// ]);
+///
+/// let expected = "\
+/// termstyle
+/// ├── ansi_term
+/// ├── serde
+/// │   └── serde_derive
+/// └── csv …
+/// ";
+///
+/// let mut result = Vec::new();
+/// paint_width(&mut result, &[El::Tree(tree)], 80).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Tree {
+    roots: Vec<Node>,
+    ascii: bool,
+    guide_style: Style,
+    max_depth: Option<usize>,
+}
+
+impl Tree {
+    /// Create a tree with the given roots.
+    pub fn new(roots: Vec<Node>) -> Tree {
+        Tree {
+            roots,
+            ..Tree::default()
+        }
+    }
+
+    /// Draw the guides (and the collapsed marker) with ascii characters.
+    pub fn ascii(mut self, ascii: bool) -> Tree {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Set the style (i.e. the color) of the guides.
+    pub fn guide_style(mut self, style: Style) -> Tree {
+        self.guide_style = style;
+        self
+    }
+
+    /// Only show `depth` levels below the roots, collapsing the nodes at the last level.
+    pub fn max_depth(mut self, depth: usize) -> Tree {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    pub fn get_roots(&self) -> &[Node] {
+        &self.roots
+    }
+
+    /// Recursively clears _all_ formatting.
+    pub fn set_plain(&mut self) {
+        for root in &mut self.roots {
+            root.set_plain();
+        }
+        self.guide_style = Style::new();
+    }
+
+    pub(crate) fn lines(&self, width: Option<usize>) -> Vec<Line> {
+        let width = width.unwrap_or(DEFAULT_WIDTH);
+        let mut out = Vec::new();
+        for root in &self.roots {
+            self.node_lines(&mut out, root, &mut Vec::new(), width);
+        }
+        out
+    }
+
+    /// Push the lines of the node and its children.
+    ///
+    /// `last` is whether each ancestor (excluding the roots) is the last of its siblings, which
+    /// decides the guides before the node.
+    fn node_lines(&self, out: &mut Vec<Line>, node: &Node, last: &mut Vec<bool>, width: usize) {
+        let (branch, end, pipe, space) = if self.ascii {
+            ("|-- ", "`-- ", "|   ", "    ")
+        } else {
+            ("├── ", "└── ", "│   ", "    ")
+        };
+        let guide = |s: String| Text::new(s).with_style(&self.guide_style);
+
+        // the guides before the first line of the label, and before the other lines
+        let mut first = String::new();
+        let mut rest = String::new();
+        for (i, &is_last) in last.iter().enumerate() {
+            if i + 1 == last.len() {
+                first.push_str(if is_last { end } else { branch });
+                rest.push_str(if is_last { space } else { pipe });
+            } else {
+                let s = if is_last { space } else { pipe };
+                first.push_str(s);
+                rest.push_str(s);
+            }
+        }
+
+        let depth = last.len();
+        let collapsed = !node.children.is_empty()
+            && (node.collapsed || self.max_depth.map(|m| depth >= m).unwrap_or(false));
+        let mut label = node.label.clone();
+        if collapsed {
+            label.push(guide(if self.ascii { " ..." } else { " …" }.into()));
+        }
+        let label_width = width.saturating_sub(layout::str_width(&first));
+        let lines = inline_lines(&label)
+            .iter()
+            .flat_map(|l| layout::wrap_line(l, label_width))
+            .collect::<Vec<_>>();
+        for (i, line) in lines.into_iter().enumerate() {
+            let prefix = if i == 0 { &first } else { &rest };
+            let mut l = Vec::new();
+            let guides = prefix.trim_end();
+            if !guides.is_empty() {
+                l.push(guide(guides.to_string()));
+            }
+            if !line.is_empty() {
+                layout::pad_line(&mut l, layout::str_width(prefix));
+            }
+            l.extend(line);
+            out.push(l);
+        }
+
+        if collapsed {
+            return;
+        }
+        for (i, child) in node.children.iter().enumerate() {
+            last.push(i + 1 == node.children.len());
+            self.node_lines(out, child, last, width);
+            last.pop();
+        }
+    }
+}
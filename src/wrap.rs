@@ -0,0 +1,183 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Wrapping styled text to a width.
+
+use std::io;
+use std_prelude::*;
+
+use layout::{self, repeat, Line};
+use Text;
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+/// Reflow a sequence of `Text` spans to a width, wrapping at word boundaries.
+///
+/// Each `\n` in the texts starts a new paragraph, which is wrapped on its own. Styles are kept
+/// across wrap points: a bold span which is wrapped is bold on both lines. Since each span is
+/// painted (and reset) on its own, escape sequences are never split or left open at the end of a
+/// line.
+///
+/// Words longer than the width are split. The whitespace at a wrap point is dropped.
+///
+/// # Examples
+/// ```rust
+/// # extern crate termstyle;
+/// use termstyle::*;
+///
+/// # fn main() {
+/// let texts = vec![
+///     Text::new("- ".into()),
+///     Text::new("termstyle".into()).bold(),
+///     Text::new(" makes it easy to build and test styled command line applications".into()),
+/// ];
+///
+/// let expected = "\
+/// - termstyle makes it
+///   easy to build and
+///   test styled command
+///   line applications
+/// ";
+///
+/// let mut result = Vec::new();
+/// Wrap::new(22).hanging(2).paint_plain(&mut result, &texts).unwrap();
+/// assert_eq!(expected, String::from_utf8(result).unwrap());
+/// # }
+/// ```
+pub struct Wrap {
+    width: usize,
+    indent: usize,
+    hanging: usize,
+    justify: bool,
+}
+
+impl Wrap {
+    /// Wrap the text to `width` columns (including any indentation).
+    ///
+    /// Use [`term_width`](fn.term_width.html) to wrap to the width of the terminal.
+    pub fn new(width: usize) -> Wrap {
+        Wrap {
+            width,
+            ..Wrap::default()
+        }
+    }
+
+    /// Indent the first line of each paragraph by `indent` spaces.
+    pub fn indent(mut self, indent: usize) -> Wrap {
+        self.indent = indent;
+        self
+    }
+
+    /// Indent the lines after the first line of each paragraph by `hanging` spaces.
+    pub fn hanging(mut self, hanging: usize) -> Wrap {
+        self.hanging = hanging;
+        self
+    }
+
+    /// Stretch the spaces between words so that every line, except the last line of each
+    /// paragraph, is exactly as wide as the width.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # extern crate termstyle;
+    /// use termstyle::*;
+    ///
+    /// # fn main() {
+    /// let texts = vec![Text::new("the quick brown fox jumps over the lazy dog".into())];
+    ///
+    /// let expected = "\
+    /// the   quick  brown
+    /// fox jumps over the
+    /// lazy dog
+    /// ";
+    ///
+    /// let mut result = Vec::new();
+    /// Wrap::new(18).justify(true).paint_plain(&mut result, &texts).unwrap();
+    /// assert_eq!(expected, String::from_utf8(result).unwrap());
+    /// # }
+    /// ```
+    pub fn justify(mut self, justify: bool) -> Wrap {
+        self.justify = justify;
+        self
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Wrap the texts into lines of styled text, which do not contain any `\n`.
+    pub fn lines(&self, texts: &[Text]) -> Vec<Vec<Text>> {
+        let mut out = Vec::new();
+        for paragraph in layout::split_lines(texts) {
+            let first = self.width.saturating_sub(self.indent);
+            let rest = self.width.saturating_sub(self.hanging);
+            let mut lines = layout::wrap_line_hanging(&paragraph, first, rest);
+            let last = lines.len() - 1;
+            for (i, line) in lines.iter_mut().enumerate() {
+                let (indent, width) = if i == 0 {
+                    (self.indent, first)
+                } else {
+                    (self.hanging, rest)
+                };
+                if self.justify && i < last {
+                    justify_line(line, width);
+                }
+                if indent > 0 && !line.is_empty() {
+                    line.insert(0, Text::new(repeat(' ', indent)));
+                }
+            }
+            out.extend(lines);
+        }
+        out
+    }
+
+    /// Paint the wrapped texts, ending each line with a newline.
+    pub fn paint<W: io::Write>(&self, w: &mut W, texts: &[Text]) -> io::Result<()> {
+        layout::paint_lines(w, &self.lines(texts))
+    }
+
+    /// Paint the wrapped texts without any styling.
+    pub fn paint_plain<W: io::Write>(&self, w: &mut W, texts: &[Text]) -> io::Result<()> {
+        let mut lines = self.lines(texts);
+        for text in lines.iter_mut().flat_map(|l| l.iter_mut()) {
+            text.set_plain();
+        }
+        layout::paint_lines(w, &lines)
+    }
+}
+
+/// Widen the gaps between the words of the line until it is `width` wide.
+///
+/// The gaps on the left are widened first.
+fn justify_line(line: &mut Line, width: usize) {
+    let used = layout::line_width(line);
+    if used >= width {
+        return;
+    }
+    // The last text of each gap after a word (so leading whitespace is not a gap).
+    let is_space = |t: &Text| !t.get_text().is_empty() && t.get_text().trim().is_empty();
+    let is_word = |t: &Text| !t.get_text().trim().is_empty();
+    let gaps: Vec<usize> = (1..line.len())
+        .filter(|&i| is_space(&line[i]) && is_word(&line[i - 1]))
+        .map(|i| {
+            let mut end = i;
+            while end + 1 < line.len() && is_space(&line[end + 1]) {
+                end += 1;
+            }
+            end
+        })
+        .collect();
+    if gaps.is_empty() {
+        return;
+    }
+    let extra = width - used;
+    for (n, &i) in gaps.iter().enumerate() {
+        let add = extra / gaps.len() + if n < extra % gaps.len() { 1 } else { 0 };
+        let text = format!("{}{}", line[i].get_text(), repeat(' ', add));
+        line[i] = line[i].with_text(text);
+    }
+}
@@ -0,0 +1,791 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Block element tests
+
+#[macro_use]
+extern crate pretty_assertions;
+
+extern crate serde_yaml;
+extern crate termstyle;
+
+use termstyle::*;
+
+pub fn from_yaml(s: &str) -> Vec<El> {
+    from_str(serde_yaml::from_str, s).unwrap()
+}
+
+/// Paint the elements in `width` with all formatting cleared.
+pub fn paint_plain(mut els: Vec<El>, width: usize) -> String {
+    for el in &mut els {
+        el.set_plain();
+    }
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &els, width).unwrap();
+    String::from_utf8(result).unwrap()
+}
+
+#[test]
+fn block_deserialize() {
+    let yaml_raw = r#"
+- heading: ["The ", {t: "Title", c: blue}]
+- heading: "Usage"
+  level: 2
+  style: {}
+- rule: ""
+- rule: "="
+  style: {d: true}
+- indent: 2
+  items:
+  - "nested\n"
+- paragraph: "some text"
+  hanging: 4
+"#;
+    let expected = vec![
+        El::Heading(Heading::new(
+            1,
+            vec![
+                Text::new("The ".into()),
+                Text::new("Title".into()).color(Color::Blue),
+            ],
+        )),
+        El::Heading(Heading::new(2, vec![Text::new("Usage".into())]).style(Style::new())),
+        El::Rule(Rule::new()),
+        El::Rule(Rule::new().c('=').style(Style::new().dim())),
+        El::Indent(2, vec![El::plain("nested\n".into())]),
+        El::Paragraph(Paragraph::new(vec![Text::new("some text".into())]).hanging(4)),
+    ];
+    assert_eq!(expected, from_yaml(yaml_raw));
+}
+
+#[test]
+fn block_default_width() {
+    // Without a width blocks are laid out in 80 columns, whatever the terminal is.
+    let mut rule = El::Rule(Rule::new());
+    rule.set_plain();
+    let mut result: Vec<u8> = Vec::new();
+    rule.paint(&mut result).unwrap();
+    assert_eq!(format!("{}\n", "─".repeat(80)), String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn block_paint() {
+    let yaml_raw = r#"
+- heading: "termstyle"
+- paragraph: "A library for building and testing styled command line applications."
+- "\n"
+- heading: "Example"
+  level: 2
+- indent: 4
+  items:
+  - "$ cargo run\n"
+  - table:
+    - ["a", "1"]
+    - ["b", "2"]
+  - indent: 2
+    items:
+    - paragraph: "nested paragraphs are wrapped in the remaining width"
+- rule: "-"
+- heading: "Level three"
+  level: 3
+"#;
+    let expected = "\
+termstyle
+═════════
+A library for building and testing
+styled command line applications.
+
+Example
+───────
+    $ cargo run
+    a 1
+    b 2
+      nested paragraphs are wrapped
+      in the remaining width
+------------------------------------
+Level three
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 36));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_heading_styled() {
+    let els = vec![
+        El::Heading(Heading::new(2, vec![Text::new("Hi".into()).color(Color::Red)])),
+        El::Rule(Rule::new().c('~').style(Style::new().color(Color::Green))),
+    ];
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &els, 4).unwrap();
+    let expected = b"\
+\x1b[1;31mHi\x1b[0m\n\
+\xe2\x94\x80\xe2\x94\x80\n\
+\x1b[32m~~~~\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_list() {
+    let yaml_raw = r#"
+- list:
+  - "build the project"
+  - ["run ", {t: "all", b: true}, " the tests\nin every crate"]
+  - - "check the results:\n"
+    - table:
+      - ["ok", "12"]
+      - ["failed", "0"]
+    - list: ["no warnings", ["no errors, which is checked by the ci", {list: [deep]}]]
+      bullets: ["-", "*"]
+  ordered: true
+  style: {b: true}
+"#;
+    let expected = "\
+1. build the project
+2. run all the tests
+   in every crate
+3. check the results:
+   ok     12
+   failed 0
+   * no warnings
+   * no errors, which is
+     checked by the ci
+     ▪ deep
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 24));
+}
+
+#[test]
+fn block_panel() {
+    let yaml_raw = r#"
+- panel:
+  - "Results:\n"
+  - table:
+    - ["passed", "12"]
+    - ["failed", "0"]
+  title: ["Summary of ", {t: "a very long title", b: true}]
+  border: ascii
+  padding: 2
+- panel: "plain"
+  border: none
+  title: "No border"
+- panel: {list: ["one", "two"]}
+  callout: note
+"#;
+    let expected = "\
++- Summary of a very… -+
+|  Results:            |
+|  passed 12           |
+|  failed 0            |
++----------------------+
+  No border
+  plain
+╭─ Note ───────────────╮
+│ • one                │
+│ • two                │
+╰──────────────────────╯
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 24));
+}
+
+#[test]
+fn block_panel_overflow() {
+    let yaml_raw = r#"
+- panel:
+  - code: "let answer = compute_the_answer(42);"
+  border: ascii
+- panel:
+  - code: "let answer = compute_the_answer(42);"
+  border: none
+"#;
+    let expected = "\
++----------------------+
+| │ let answer = comp… |
++----------------------+
+  │ let answer = comp…
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 24));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_panel_styled() {
+    let els = vec![El::Panel(Panel::error(vec![El::plain("x".into())]).padding(0))];
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &els, 12).unwrap();
+    let expected = "\
+\x1b[31m╭\x1b[0m\x1b[31m─ \x1b[0m\x1b[1;31mError\x1b[0m \x1b[31m──\x1b[0m\x1b[31m╮\x1b[0m
+\x1b[31m│\x1b[0mx         \x1b[31m│\x1b[0m
+\x1b[31m╰──────────╯\x1b[0m
+";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_columns() {
+    let yaml_raw = r#"
+- columns:
+  - - panel: "legend"
+      padding: 0
+  - [{list: ["first", "second item"]}]
+  - "a long word: abcdefghijklmnop"
+  widths: [{fixed: 8}, {ratio: 2}, {ratio: 1}]
+  gap: 1
+"#;
+    let expected = "\
+╭──────╮ • first        a long
+│legend│ • second item  word:
+╰──────╯                abcdefg
+                        hijklmn
+                        op
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 31));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_columns_styled() {
+    let columns = Columns::new()
+        .column(Width::Fixed(3), vec![El::Text(Text::new("abcdef".into()).bold())])
+        .column(Width::Fixed(3), vec![El::Text(Text::new("x".into()).color(Color::Red))]);
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &[El::Columns(columns)], 80).unwrap();
+    let expected = b"\
+\x1b[1mabc\x1b[0m  \x1b[31mx\x1b[0m\n\
+\x1b[1mdef\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_tree() {
+    let yaml_raw = r#"
+- tree:
+  - label: "src"
+    tree:
+    - label: "bin"
+      tree: ["main.rs"]
+    - label: "a label with\nmultiple lines"
+      tree: ["deep", "deeper"]
+    - label: "hidden"
+      tree: ["x"]
+      collapsed: true
+  - "README.md"
+  ascii: true
+- tree:
+  - label: "root"
+    tree:
+    - label: "a"
+      tree: ["b"]
+    - "c, which is long enough to wrap"
+  max_depth: 1
+"#;
+    let expected = "\
+src
+|-- bin
+|   `-- main.rs
+|-- a label with
+|   multiple lines
+|   |-- deep
+|   `-- deeper
+`-- hidden ...
+README.md
+root
+├── a …
+└── c, which is long
+    enough to wrap
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 20));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_tree_styled() {
+    let tree = Tree::new(vec![
+        Node::new(vec![Text::new("a".into())]).child(Node::new(vec![Text::new("b".into()).bold()])),
+    ]).guide_style(Style::new().dim());
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &[El::Tree(tree)], 80).unwrap();
+    let expected = "a\n\x1b[2m└──\x1b[0m \x1b[1mb\x1b[0m\n";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_key_value() {
+    let yaml_raw = r#"
+- key_value:
+  - ["Status", {t: "running", c: green}]
+  - ["Uptime", "3 days"]
+  - [["Last ", {t: "error", c: red}], "connection refused\n(retrying in 5s)"]
+  separator: " = "
+  align: left
+"#;
+    let expected = "\
+Status     = running
+Uptime     = 3 days
+Last error = connection
+             refused
+             (retrying in 5s)
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 29));
+
+    // the keys are right aligned by default
+    assert_eq!(Align::Right, Align::default());
+    assert_eq!(KeyValue::new().align(Align::default()), KeyValue::new());
+}
+
+#[test]
+fn block_gauge() {
+    let yaml_raw = r#"
+- gauge: 0.5
+  width: 10
+- gauge: 0.3125
+  width: 4
+  blocks: true
+  brackets: true
+- gauge: 1.5
+  width: 4
+  fill: "="
+  empty: " "
+  label: "done"
+"#;
+    let expected = "\
+[#####-----]  50%
+[█▎  ]  31%
+[====] done
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 80));
+
+    let els = vec![El::Gauge(Gauge::progress(3, 12).width(8).brackets(false))];
+    assert_eq!("##------ 3/12\n", paint_plain(els, 80));
+}
+
+#[test]
+fn block_gauge_eq() {
+    // Eq is a total equality, even for NaN thresholds.
+    let gauge = Gauge::new(0.5).threshold(f64::NAN, Style::new().bold());
+    assert_eq!(gauge, gauge.clone());
+    assert_ne!(gauge, Gauge::new(0.5).threshold(0.9, Style::new().bold()));
+    assert_ne!(Gauge::new(0.0), Gauge::new(0.5));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_gauge_styled() {
+    let gauge = |r| Gauge::new(r).width(2).percent(false).utilization().texts();
+    let mut result: Vec<u8> = Vec::new();
+    for ratio in &[0.5, 0.75, 0.95] {
+        for t in gauge(*ratio) {
+            t.paint(&mut result).unwrap();
+        }
+    }
+    let expected = "[\x1b[32m#\x1b[0m-][\x1b[33m##\x1b[0m][\x1b[31m##\x1b[0m]";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_charts() {
+    let yaml_raw = r#"
+- sparkline: [0, 1, 2, 3, 4, .nan, 8, 7]
+- sparkline: [0, 4, 8]
+  ascii: true
+  max: 16
+- bar_chart:
+  - ["read", 3]
+  - [["write ", {t: "(MB/s)", c: blue}], 1.25]
+  width: 6
+  max: 4
+  precision: 1
+- bar_chart:
+  - ["a", 1]
+  - ["b", 2]
+  ascii: true
+"#;
+    let expected = "\
+▁▂▃▄▅ █▇
+_,=
+read         ████▌  3.0
+write (MB/s) █▉     1.2
+a ######      1
+b ########### 2
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 15));
+
+    let spark = Sparkline::new(vec![1.0, 2.0, 1.0]).ascii(true);
+    let chart = BarChart::new()
+        .bar(vec![Text::new("x".into())], 1.0)
+        .bar(vec![Text::new("y".into())], 0.5)
+        .width(2)
+        .ascii(true);
+    let mut table = Table::new(vec![vec![spark.texts(), chart.texts()]]);
+    table.set_plain();
+    let mut result = Vec::new();
+    table.paint(&mut result).unwrap();
+    assert_eq!("_#_ x ##   1\n    y #  0.5\n", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn block_charts_eq() {
+    // Eq is a total equality, even for NaN values.
+    let yaml_raw = r#"
+- sparkline: [1, .nan, 2]
+- bar_chart:
+  - ["nan", .nan]
+"#;
+    assert_eq!(from_yaml(yaml_raw), from_yaml(yaml_raw));
+
+    let spark = Sparkline::new(vec![1.0, f64::NAN]);
+    assert_eq!(spark, spark.clone());
+    assert_ne!(spark, Sparkline::new(vec![1.0, 2.0]));
+    let chart = BarChart::new().bar(vec![Text::new("x".into())], f64::NAN);
+    assert_eq!(chart, chart.clone());
+    assert_ne!(chart, BarChart::new().bar(vec![Text::new("y".into())], f64::NAN));
+}
+
+#[test]
+fn block_code() {
+    let yaml_raw = r##"
+- code: "{\"a\": [1, true]}\n"
+  language: json
+  gutter: false
+- code: "# config\nname: term style # inline\nitems:\n  - 'x'\n  - 2.5\n  - this line is much too long\n"
+  language: yaml
+  line_numbers: true
+  first_line: 8
+  marks: [[9, 10]]
+"##;
+    let expected = "\
+{\"a\": [1, true]}
+ 8 │ # config
+ 9 > name: term style # inline
+10 > items:
+11 │   - 'x'
+12 │   - 2.5
+13 │   - this line is much to…
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 30));
+}
+
+#[test]
+fn code_lexer() {
+    use Token::*;
+    let lexer = Lexer::default();
+    assert_eq!(
+        lexer.tokens("json", r#"{"a": "b\"", "c": -1.5e3}"#),
+        vec![
+            (Punctuation, "{"),
+            (Key, "\"a\""),
+            (Punctuation, ":"),
+            (Plain, " "),
+            (String, "\"b\\\"\""),
+            (Punctuation, ","),
+            (Plain, " "),
+            (Key, "\"c\""),
+            (Punctuation, ":"),
+            (Plain, " "),
+            (Number, "-1.5e3"),
+            (Punctuation, "}"),
+        ]
+    );
+    assert_eq!(
+        lexer.tokens("yaml", "- key word: no # yes"),
+        vec![
+            (Punctuation, "-"),
+            (Plain, " "),
+            (Key, "key word"),
+            (Punctuation, ":"),
+            (Plain, " "),
+            (Keyword, "no"),
+            (Plain, " "),
+            (Comment, "# yes"),
+        ]
+    );
+    assert_eq!(
+        lexer.tokens("toml", "a.b = [0x1F, 'c'] # d"),
+        vec![
+            (Key, "a.b"),
+            (Plain, " "),
+            (Punctuation, "="),
+            (Plain, " "),
+            (Punctuation, "["),
+            (Number, "0x1F"),
+            (Punctuation, ","),
+            (Plain, " "),
+            (String, "'c'"),
+            (Punctuation, "]"),
+            (Plain, " "),
+            (Comment, "# d"),
+        ]
+    );
+    assert_eq!(lexer.tokens("toml", " [dependencies]")[1], (Key, "[dependencies]"));
+    assert_eq!(lexer.tokens("rust", "fn main() {}"), vec![(Plain, "fn main() {}")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn block_code_styled() {
+    let code = Code::new("json", "[1, null]").gutter(false);
+    let mut result: Vec<u8> = Vec::new();
+    paint(&mut result, &[El::Code(code)]).unwrap();
+    let expected = "[\x1b[36m1\x1b[0m, \x1b[35mnull\x1b[0m]\n";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_diagnostic() {
+    let yaml_raw = r#"
+- diagnostic: "duplicate key `name`"
+  severity: warning
+  path: "Cargo.toml"
+  source: "name = \"a\"\nversion = \"1\"\n\n\n\n\n\n\n\tname = \"b\"\n"
+  first_line: 2
+  labels:
+  - {line: 10, col: 2, len: 4, label: "defined again here"}
+  - {line: 10, col: 9, len: 3}
+  - {line: 2, col: 1, len: 4, label: "first defined here", secondary: true}
+  help: ["remove one of them"]
+- diagnostic: "no source"
+"#;
+    let expected = "\
+warning: duplicate key `name`
+  --> Cargo.toml:10:2
+   |
+ 2 | name = \"a\"
+   | ---- first defined here
+...
+10 |     name = \"b\"
+   |     ^^^^ defined again here
+   |            ^^^
+   |
+   = help: remove one of them
+error: no source
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 80));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_diagnostic_styled() {
+    let diag = Diagnostic::error(vec![Text::new("bad".into())])
+        .source("x", 1)
+        .label(Label::primary(1, 1, 1, Vec::new()));
+    let mut result: Vec<u8> = Vec::new();
+    paint(&mut result, &[El::Diagnostic(diag)]).unwrap();
+    let expected = "\
+\x1b[1;31merror\x1b[0m\x1b[1m: \x1b[0m\x1b[1mbad\x1b[0m
+\x1b[1;34m  |\x1b[0m
+\x1b[1;34m1 |\x1b[0m x
+\x1b[1;34m  |\x1b[0m \x1b[1;31m^\x1b[0m
+";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_diff() {
+    let yaml_raw = r#"
+- diff:
+  - "1\n2\n3\n4\n5\n6\n7\n8\n"
+  - "0\n1\n2\n3\n4\n5\n6\nseven\n8\n"
+  context: 1
+- diff:
+  - "same\nlet x = 1;\nremoved\n"
+  - "same\nlet y = 1;\nadded\nmore\n"
+  side_by_side: true
+- diff: ["equal", "equal"]
+"#;
+    let expected = "\
+@@ -1,1 +1,2 @@
++0
+ 1
+@@ -6,3 +7,3 @@
+ 6
+-7
++seven
+ 8
+@@ -1,3 +1,4 @@
+same          same
+let x = 1;  | let y = 1;
+removed     | added
+            > more
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw), 25));
+}
+
+#[cfg(unix)]
+#[test]
+fn block_diff_styled() {
+    let diff = Diff::new("a b\n", "a c\n").side_by_side(true);
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &[El::Diff(diff)], 11).unwrap();
+    let expected = "\
+\x1b[36m@@ -1,1 +1,1 @@\x1b[0m
+\x1b[31ma \x1b[0m\x1b[1;41;37mb\x1b[0m  | \x1b[32ma \x1b[0m\x1b[1;42;37mc\x1b[0m
+";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn block_styles() {
+    let yaml_raw = r#"
+- styles:
+    error: {b: true, c: red}
+- list:
+  - [{t: "bad", class: error, c: yellow}, {t: " ok", class: ok}]
+- table:
+  - [{t: "cell", class: error}, {t: "unknown", class: nope, i: true}]
+"#;
+    let styles = Stylesheet::new()
+        .class("error", Style::new().color(Color::Blue))
+        .class("ok", Style::new().dim());
+    let els = from_str_styled(serde_yaml::from_str, yaml_raw, &styles).unwrap();
+
+    let list = List::new(vec![vec![
+        El::Text(Text::new("bad".into()).bold().color(Color::Yellow)),
+        El::Text(Text::new(" ok".into()).dim()),
+    ]]);
+    let table = Table::new(vec![vec![
+        vec![Text::new("cell".into()).bold().color(Color::Red)],
+        vec![Text::new("unknown".into()).italic()],
+    ]]);
+    assert_eq!(vec![El::List(list), El::Table(table)], els);
+
+    // without the stylesheet only the document's classes are known
+    let els = from_yaml(yaml_raw);
+    match els[0] {
+        El::List(ref l) => assert_eq!(
+            l.get_items()[0][1],
+            El::Text(Text::new(" ok".into()))
+        ),
+        _ => panic!("expected a list"),
+    }
+}
+
+#[test]
+fn block_styles_nested() {
+    let yaml_raw = r#"
+- {t: "outer", class: note}
+- indent: 2
+  items:
+  - {t: "inner", class: note}
+  - styles:
+      note: {b: true}
+  - panel:
+    - {t: "nested", class: note}
+    - styles:
+        note: {i: true}
+    - {t: "panel", class: note, c: red}
+  - {t: "misspelled", class: noet}
+"#;
+    let styles = Stylesheet::new().class("note", Style::new().dim());
+    let els = from_str_styled(serde_yaml::from_str, yaml_raw, &styles).unwrap();
+
+    let panel = Panel::new(vec![
+        El::Text(Text::new("nested".into()).italic()),
+        El::Text(Text::new("panel".into()).italic().color(Color::Red)),
+    ]);
+    let expected = vec![
+        El::Text(Text::new("outer".into()).dim()),
+        El::Indent(
+            2,
+            vec![
+                El::Text(Text::new("inner".into()).bold()),
+                El::Panel(panel),
+                El::plain("misspelled".into()),
+            ],
+        ),
+    ];
+    assert_eq!(expected, els);
+}
+
+#[test]
+fn code_lexer_whitespace() {
+    use Token::*;
+    let lexer = Lexer::default();
+    assert_eq!(
+        lexer.tokens("json", "{\"a\": 1}\r"),
+        vec![
+            (Punctuation, "{"),
+            (Key, "\"a\""),
+            (Punctuation, ":"),
+            (Plain, " "),
+            (Number, "1"),
+            (Punctuation, "}"),
+            (Plain, "\r"),
+        ]
+    );
+    assert_eq!(
+        lexer.tokens("yaml", "a: \u{3000}"),
+        vec![(Key, "a"), (Punctuation, ":"), (Plain, " \u{3000}")]
+    );
+    assert_eq!(lexer.tokens("toml", "x = \"\\é\""), vec![
+        (Key, "x"),
+        (Plain, " "),
+        (Punctuation, "="),
+        (Plain, " "),
+        (String, "\"\\é\""),
+    ]);
+
+    let code = Code::new("json", "{\"a\": 1}\r\n[2]\r\n").gutter(false);
+    assert_eq!("{\"a\": 1}\n[2]\n", paint_plain(vec![El::Code(code)], 80));
+}
+
+#[test]
+fn block_diff_no_newline() {
+    assert!(Diff::new("a\n", "a\n").is_empty());
+    assert!(!Diff::new("a\n", "a").is_empty());
+
+    let expected = "\
+@@ -1,2 +1,2 @@
+ a
+-b
++b
+\\ No newline at end of file
+";
+    assert_eq!(expected, paint_plain(vec![El::Diff(Diff::new("a\nb\n", "a\nb"))], 80));
+
+    let expected = "\
+@@ -1,1 +1,2 @@
+-a
+\\ No newline at end of file
++a
++b
+";
+    let diff = Diff::new("a", "a\nb\n");
+    assert_eq!(expected, paint_plain(vec![El::Diff(diff)], 80));
+}
+
+#[test]
+fn block_diff_full_context() {
+    let expected = "\
+@@ -1,5 +1,5 @@
+ a
+-b
++c
+ d
+ e
+ f
+";
+    let diff = Diff::new("a\nb\nd\ne\nf\n", "a\nc\nd\ne\nf\n").context(usize::MAX);
+    assert_eq!(expected, paint_plain(vec![El::Diff(diff)], 80));
+
+    let expected = "\
+@@ -1,2 +1,2 @@
+a                    a
+b                  | c
+\\ No newline at end of file
+";
+    let diff = Diff::new("a\nb", "a\nc").context(usize::MAX).side_by_side(true);
+    assert_eq!(expected, paint_plain(vec![El::Diff(diff)], 40));
+}
@@ -0,0 +1,600 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Table tests
+
+#[macro_use]
+extern crate pretty_assertions;
+
+extern crate serde_yaml;
+extern crate termstyle;
+
+use termstyle::*;
+
+pub fn from_yaml(s: &str) -> Vec<El> {
+    from_str(serde_yaml::from_str, s).unwrap()
+}
+
+/// Paint the elements with all formatting cleared.
+pub fn paint_plain(mut els: Vec<El>) -> String {
+    for el in &mut els {
+        el.set_plain();
+    }
+    let mut result: Vec<u8> = Vec::new();
+    paint(&mut result, &els).unwrap();
+    String::from_utf8(result).unwrap()
+}
+
+fn cell(s: &str) -> Vec<Text> {
+    vec![Text::new(s.into())]
+}
+
+#[test]
+fn table_header_footer() {
+    let yaml_raw = r#"
+- table:
+  - ["foo", "1"]
+  - ["barbaz", "22"]
+  header: ["name", "size"]
+  footer: ["total", "23"]
+"#;
+    let elements = from_yaml(yaml_raw);
+    let expected = vec![
+        El::Table(
+            Table::new(vec![
+                vec![cell("foo"), cell("1")],
+                vec![cell("barbaz"), cell("22")],
+            ]).header(vec![cell("name"), cell("size")])
+                .footer(vec![cell("total"), cell("23")]),
+        ),
+    ];
+    assert_eq!(expected, elements);
+
+    let expected = "\
+name   size
+───────────
+foo    1
+barbaz 22
+───────────
+total  23
+";
+    assert_eq!(expected, paint_plain(elements));
+}
+
+#[cfg(unix)]
+#[test]
+fn table_header_footer_styled() {
+    let table = Table::new(vec![vec![cell("a"), cell("b")]])
+        .header(vec![cell("x"), cell("y")])
+        .footer(vec![cell("f"), cell("g")])
+        .separators(false);
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = b"\
+\x1b[1mx\x1b[0m \x1b[1my\x1b[0m\n\
+a b\n\
+\x1b[2mf\x1b[0m \x1b[2mg\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn table_border() {
+    let yaml_raw = r#"
+- table:
+  - ["foo", "1"]
+  - ["barbaz"]
+  header: ["name", "size"]
+  border: light
+"#;
+    let expected = "\
+┌────────┬──────┐
+│ name   │ size │
+├────────┼──────┤
+│ foo    │ 1    │
+│ barbaz │      │
+└────────┴──────┘
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw)));
+}
+
+#[test]
+fn table_max_width() {
+    let yaml_raw = r#"
+- table:
+  - ["the quick brown fox", "jumped over the lazy dog", "/home/user/projects/termstyle"]
+  columns:
+  - {max_width: 9}
+  - {max_width: 10, overflow: truncate}
+  - {max_width: 15, overflow: truncate_middle}
+"#;
+    let expected = "\
+the quick jumped ov… /home/u…rmstyle
+brown fox
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw)));
+}
+
+#[cfg(unix)]
+#[test]
+fn table_wrap_keeps_style() {
+    let table = Table::new(vec![
+        vec![
+            vec![
+                Text::new("plain ".into()),
+                Text::new("bold words".into()).bold(),
+            ],
+            cell("x"),
+        ],
+    ]).column(0, Column::new().max_width(10));
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = b"\
+plain \x1b[1mbold\x1b[0m x\n\
+\x1b[1mwords\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn table_fit_width() {
+    let mut table = Table::new(vec![
+        vec![cell("id"), cell("a fairly long name"), cell("/a/long/path/to/a/file")],
+    ]).header(vec![cell("ID"), cell("NAME"), cell("PATH")])
+        .border(Border::Ascii)
+        .column(0, Column::new().priority(1))
+        .column(1, Column::new().min_width(8))
+        .column(2, Column::new().overflow(Overflow::TruncateMiddle));
+    table.set_plain();
+
+    let mut result: Vec<u8> = Vec::new();
+    table.paint_width(&mut result, 26).unwrap();
+    let expected = "\
++----+----------+--------+
+| ID | NAME     | PATH   |
++----+----------+--------+
+| id | a fairly | /a/…le |
+|    | long     |        |
+|    | name     |        |
++----+----------+--------+
+";
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn table_multiline_cells() {
+    let yaml_raw = r#"
+- table:
+  - ["one\ntwo\nthree", "a", "b"]
+  - ["x", "y\nz", "w"]
+  border: ascii
+  valign: middle
+  row_valign: {1: bottom}
+"#;
+    let expected = "\
++-------+---+---+
+| one   |   |   |
+| two   | a | b |
+| three |   |   |
+|       | y |   |
+| x     | z | w |
++-------+---+---+
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw)));
+}
+
+#[test]
+fn table_multiline_cells_borderless() {
+    let table = Table::new(vec![
+        vec![cell("a"), cell("multiple\nlines"), cell("b")],
+        vec![cell("c"), cell("d"), cell("e")],
+    ]);
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = "\
+a multiple b
+  lines
+c d        e
+";
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn table_spans() {
+    let yaml_raw = r#"
+- table:
+  - [{cell: "GET", rowspan: 2}, "/", "1ms", "10ms"]
+  - ["/about", "2ms", "200ms"]
+  - ["POST", "/login", {cell: "timed out", colspan: 2}]
+  headers:
+  - ["", "", {cell: "Latency", colspan: 2}]
+  - ["Method", "Route", "p50", "p99"]
+  border: light
+"#;
+    let expected = "\
+┌────────┬────────┬─────────────┐
+│        │        │ Latency     │
+│ Method │ Route  │ p50 │ p99   │
+├────────┼────────┼─────┼───────┤
+│ GET    │ /      │ 1ms │ 10ms  │
+│        │ /about │ 2ms │ 200ms │
+│ POST   │ /login │ timed out   │
+└────────┴────────┴─────────────┘
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw)));
+}
+
+#[test]
+fn table_spans_widen_columns() {
+    let table = Table::new(vec![
+        vec![cell("a"), cell("b"), cell("c")],
+        vec![cell("a wide cell"), cell("d")],
+        vec![cell("tall\ncell\nhere"), cell("e"), cell("f")],
+        vec![cell("g"), cell("h")],
+    ]).span(1, 0, 2, 1)
+        .span(2, 0, 1, 2)
+        .valign(VAlign::Bottom);
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = "\
+a       b   c
+a wide cell d
+tall    e   f
+cell
+here    g   h
+";
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn table_spacing() {
+    let yaml_raw = r#"
+- "- item:\n"
+- table:
+  - ["foo", "1", "x"]
+  - ["barbaz", "22", "y"]
+  header: ["name", "size", "z"]
+  gap: 3
+  padding: 1
+  indent: 4
+"#;
+    let expected = "\
+- item:
+     name       size     z
+    ───────────────────────
+     foo        1        x
+     barbaz     22       y
+";
+    assert_eq!(expected, paint_plain(from_yaml(yaml_raw)));
+}
+
+#[cfg(unix)]
+#[test]
+fn table_stripe_and_rules() {
+    let yaml_raw = r#"
+- table:
+  - ["build", "OK"]
+  - ["test", "FAIL"]
+  - ["lint", "WARN"]
+  header: ["step", "status"]
+  separators: false
+  header_style: {}
+  stripe: {bg: black}
+  cell_rules:
+  - {column: status, equals: FAIL, style: {b: true, c: red}}
+  - {column: 1, contains: WARN, style: {c: yellow}, row: true}
+"#;
+    let mut result: Vec<u8> = Vec::new();
+    paint(&mut result, &from_yaml(yaml_raw)).unwrap();
+    let expected = b"\
+step  status\n\
+build OK\n\
+\x1b[40mtest\x1b[0m\x1b[40m  \x1b[0m\x1b[1;40;31mFAIL\x1b[0m\n\
+\x1b[33mlint\x1b[0m\x1b[33m  \x1b[0m\x1b[33mWARN\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[cfg(unix)]
+#[test]
+fn table_style_hooks() {
+    let table = Table::new(vec![
+        vec![cell("a"), cell("1")],
+        vec![cell("b"), cell("2")],
+    ]).row_style(|r, _| if r == 0 { Some(Style::new().dim()) } else { None })
+        .cell_style(|_, c, _| if c == 1 { Some(Style::new().bold()) } else { None });
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = b"\
+\x1b[2ma\x1b[0m\x1b[2m \x1b[0m\x1b[1;2m1\x1b[0m\n\
+b \x1b[1m2\x1b[0m\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}
+
+#[test]
+fn table_style_hooks_send_eq() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Table>();
+    assert_send_sync::<El>();
+
+    let table = Table::new(vec![vec![cell("a")]]).row_style(|_, _| None);
+    assert_eq!(table, table.clone());
+    assert_ne!(table, Table::new(vec![vec![cell("a")]]).row_style(|_, _| None));
+
+    let painted = ::std::thread::spawn(move || {
+        let mut result: Vec<u8> = Vec::new();
+        table.paint(&mut result).unwrap();
+        result
+    }).join()
+        .unwrap();
+    assert_eq!(b"a\n".to_vec(), painted);
+}
+
+#[test]
+fn table_from_csv() {
+    let data = "name,size\nfoo.rs,12\n\"a, b\",7\n";
+    let table = Table::from_csv(data.as_bytes())
+        .unwrap()
+        .format_column("size", |s| vec![Text::new(format!("{} B", s))]);
+    let expected = Table::new(vec![
+        vec![cell("foo.rs"), cell("12 B")],
+        vec![cell("a, b"), cell("7 B")],
+    ]).header(vec![cell("name"), cell("size")]);
+    assert_eq!(expected, table);
+
+    let tsv = Table::from_tsv("name\tsize\nfoo.rs\t12 B\na, b\t7 B\n".as_bytes()).unwrap();
+    assert_eq!(expected, tsv);
+}
+
+#[test]
+fn table_export() {
+    let yaml_raw = r#"
+- table:
+  - [{cell: "GET", rowspan: 2}, "/", ["1", {t: "ms", b: true}]]
+  - ["/about", "2ms"]
+  - ["POST", {cell: "timed\nout", colspan: 2}]
+  headers:
+  - ["", {cell: "Request", colspan: 2}]
+  - ["Method", "Route", "p50"]
+  footer: ["total", "", "3ms"]
+"#;
+    let table = match from_yaml(yaml_raw).pop().unwrap() {
+        El::Table(t) => t,
+        _ => panic!("not a table"),
+    };
+    let export = |f: fn(&Table, &mut Vec<u8>) -> ::std::io::Result<()>| {
+        let mut result = Vec::new();
+        f(&table, &mut result).unwrap();
+        String::from_utf8(result).unwrap()
+    };
+
+    let expected = "\
+Method,Route,p50
+GET,/,1ms
+,/about,2ms
+POST,\"timed
+out\",
+total,,3ms
+";
+    assert_eq!(expected, export(|t, w| t.write_csv(w)));
+
+    let expected = "\
+Method\tRoute\tp50
+GET\t/\t1ms
+\t/about\t2ms
+POST\t\"timed
+out\"\t
+total\t\t3ms
+";
+    assert_eq!(expected, export(|t, w| t.write_tsv(w)));
+
+    let expected = r#"{"rows":[{"Method":"GET","Route":"/","p50":"1ms"},{"Method":"","Route":"/about","p50":"2ms"},{"Method":"POST","Route":"timed\nout","p50":""}],"footer":[{"Method":"total","Route":"","p50":"3ms"}]}
+"#;
+    assert_eq!(expected, export(|t, w| t.write_json(w)));
+
+    let mut result = Vec::new();
+    Table::new(vec![vec![cell("a"), cell("1")]])
+        .write_json(&mut result)
+        .unwrap();
+    let expected = r#"{"rows":[["a","1"]],"footer":[]}
+"#;
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+
+    let expected = "\
+| Method | Route | p50 |
+| --- | --- | --- |
+| GET | / | 1ms |
+|  | /about | 2ms |
+| POST | timed<br>out |  |
+| total |  | 3ms |
+";
+    assert_eq!(expected, export(|t, w| t.write_markdown(w)));
+}
+
+#[test]
+fn table_sort_filter_select() {
+    let table = Table::new(vec![
+        vec![cell("b.rs"), cell("9"), cell("x")],
+        vec![cell("a.rs"), cell("10")],
+        vec![cell("c.rs"), cell("n/a"), cell("y")],
+        vec![cell("d.md"), cell("2.5"), cell("z")],
+    ]).header(vec![cell("name"), cell("size"), cell("kind")])
+        .footer(vec![cell("total"), cell("21.5"), cell("")]);
+
+    let sorted = Table::new(table.rows().to_vec())
+        .header(table.get_header()[0].clone())
+        .sort("size", Sort::Numeric);
+    let names: Vec<String> = sorted
+        .rows()
+        .iter()
+        .map(|r| r[0][0].get_text().to_string())
+        .collect();
+    assert_eq!(vec!["d.md", "b.rs", "a.rs", "c.rs"], names);
+
+    let mut table = table
+        .filter(|row| row[0][0].get_text().ends_with(".rs"))
+        .sort_desc(0, Sort::Lexicographic)
+        .select(vec![ColumnRef::from("size"), ColumnRef::from(0)]);
+    table.set_plain();
+    let mut result: Vec<u8> = Vec::new();
+    table.paint(&mut result).unwrap();
+    let expected = "\
+size name
+──────────
+n/a  c.rs
+9    b.rs
+10   a.rs
+──────────
+21.5 total
+";
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn table_sort_numeric_nan() {
+    let values = [
+        "3", "NaN", "1", "nan", "2", "6", "8", "10", "NaN", "0", "x", "-1.5", "4",
+    ];
+    let rows = values.iter().map(|v| vec![cell(v)]).collect();
+    let sorted = Table::new(rows).sort(0, Sort::Numeric);
+    let sorted: Vec<&str> = sorted.rows().iter().map(|r| r[0][0].get_text()).collect();
+    assert_eq!(
+        vec![
+            "-1.5", "0", "1", "2", "3", "4", "6", "8", "10", "NaN", "NaN", "nan", "x",
+        ],
+        sorted
+    );
+}
+
+#[test]
+fn table_stream() {
+    let rows = vec![
+        vec![cell("a"), cell("1")],
+        vec![cell("bb"), cell("22")],
+        vec![cell("a much wider cell"), cell("3")],
+        vec![cell("c"), cell("4")],
+    ];
+    let mut table = Table::new(Vec::new())
+        .header(vec![cell("name"), cell("n")])
+        .footer(vec![cell("total"), cell("30")]);
+    table.set_plain();
+    let stream = |wide_rows: WideRows| -> String {
+        let mut stream = TableStream::new(table.clone(), Vec::new())
+            .sample(2)
+            .wide_rows(wide_rows);
+        stream.write_rows(rows.clone()).unwrap();
+        String::from_utf8(stream.finish().unwrap()).unwrap()
+    };
+
+    // when everything is sampled the output is the same as painting the table
+    let mut sampled = TableStream::new(table.clone(), Vec::new()).sample(10);
+    sampled.write_rows(rows.clone()).unwrap();
+    let mut expected: Vec<u8> = Vec::new();
+    Table::new(rows.clone())
+        .header(vec![cell("name"), cell("n")])
+        .footer(vec![cell("total"), cell("30")])
+        .header_style(Style::new())
+        .footer_style(Style::new())
+        .paint(&mut expected)
+        .unwrap();
+    assert_eq!(expected, sampled.finish().unwrap());
+
+    let expected = "\
+name  n
+────────
+a     1
+bb    22
+a     3
+much
+wider
+cell
+c     4
+────────
+total 30
+";
+    assert_eq!(expected, stream(WideRows::Fit));
+
+    let expected = "\
+name  n
+────────
+a     1
+bb    22
+a much wider cell 3
+c                 4
+────────────────────
+total             30
+";
+    assert_eq!(expected, stream(WideRows::Widen));
+
+    let expected = "\
+name  n
+────────
+a     1
+bb    22
+a much wider cell 3
+c     4
+────────
+total 30
+";
+    assert_eq!(expected, stream(WideRows::Overflow));
+}
+
+#[test]
+fn table_stream_spans() {
+    let rows = vec![
+        vec![cell("GET"), cell("/"), cell("1ms")],
+        vec![cell("/about"), cell("2ms")],
+        vec![cell("POST"), cell("/login"), cell("5ms")],
+        vec![cell("PUT"), cell("/a"), cell("3ms")],
+    ];
+    let mut table = Table::new(Vec::new())
+        .border(Border::Ascii)
+        .span(0, 0, 1, 2)
+        .span(3, 0, 1, 3);
+    table.set_plain();
+
+    // the span of the first row is painted once its last row is written
+    let mut stream = TableStream::new(table, Vec::new()).widths(vec![4, 6, 3]);
+    stream.write_rows(rows).unwrap();
+    let expected = "\
++------+--------+-----+
+| GET  | /      | 1ms |
+|      | /about | 2ms |
+| POST | /login | 5ms |
+| PUT  | /a     | 3ms |
++------+--------+-----+
+";
+    assert_eq!(expected, String::from_utf8(stream.finish().unwrap()).unwrap());
+}
+
+#[test]
+fn table_stream_short_widths() {
+    let mut table = Table::new(Vec::new())
+        .header(vec![cell("name"), cell("count")])
+        .footer(vec![cell("total"), cell("30"), cell("!")]);
+    table.set_plain();
+    let mut stream = TableStream::new(table, Vec::new()).widths(vec![3]);
+    stream.write_row(vec![cell("a"), cell("1")]).unwrap();
+    stream.write_row(vec![cell("bb"), cell("22"), cell("x")]).unwrap();
+
+    let expected = "\
+nam count
+e
+───────────
+a   1
+bb  22    x
+───────────
+tot 30    !
+al
+";
+    assert_eq!(expected, String::from_utf8(stream.finish().unwrap()).unwrap());
+}
@@ -0,0 +1,75 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Role and theme tests
+
+#[macro_use]
+extern crate pretty_assertions;
+
+extern crate serde_yaml;
+extern crate termstyle;
+
+use termstyle::*;
+
+#[test]
+fn theme_roles_deserialize() {
+    let yaml_raw = r#"
+- {t: "failed", role: error}
+- [" ", {t: "skipped", role: muted, c: blue}]
+"#;
+    let els = from_str(serde_yaml::from_str, yaml_raw).unwrap();
+    let expected = vec![
+        El::Text(Text::new("failed".into()).role(Role::Error)),
+        El::plain(" ".into()),
+        El::Text(Text::new("skipped".into()).role(Role::Muted).color(Color::Blue)),
+    ];
+    assert_eq!(expected, els);
+
+    let theme: Theme = serde_yaml::from_str("success: {c: cyan}").unwrap();
+    assert_eq!(theme.get(Role::Success), Style::new().color(Color::Cyan));
+    assert_eq!(theme.get(Role::Error), Theme::dark().get(Role::Error));
+}
+
+#[cfg(unix)]
+#[test]
+fn theme_paint() {
+    let els = vec![
+        El::Text(Text::new("a".into()).role(Role::Warning)),
+        El::Text(Text::new("b".into()).role(Role::Muted).color(Color::Blue)),
+        El::Table(Table::new(vec![vec![vec![Text::new("c".into()).role(Role::Error)]]])),
+    ];
+    let paint_with = |theme: Theme| {
+        let mut result: Vec<u8> = Vec::new();
+        theme.paint_width(&mut result, &els, 80).unwrap();
+        result
+    };
+
+    let expected = "\x1b[1;33ma\x1b[0m\x1b[2;34mb\x1b[0m\x1b[1;31mc\x1b[0m\n";
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &els, 80).unwrap();
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &result);
+    assert_eq!(repr_e, repr_r);
+
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &paint_with(Theme::dark()));
+    assert_eq!(repr_e, repr_r);
+
+    let expected = "\x1b[1;35ma\x1b[0m\x1b[2;34mb\x1b[0m\x1b[1;31mc\x1b[0m\n";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &paint_with(Theme::light()));
+    assert_eq!(repr_e, repr_r);
+
+    let expected = "\x1b[1;43;30ma\x1b[0m\x1b[34mb\x1b[0m\x1b[1;41;37mc\x1b[0m\n";
+    let (repr_e, repr_r) = eprint_diff(expected.as_bytes(), &paint_with(Theme::high_contrast()));
+    assert_eq!(repr_e, repr_r);
+
+    let mut plain = els.clone();
+    for el in &mut plain {
+        el.set_plain();
+    }
+    let mut result: Vec<u8> = Vec::new();
+    paint_width(&mut result, &plain, 80).unwrap();
+    assert_eq!("abc\n", String::from_utf8(result).unwrap());
+}
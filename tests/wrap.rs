@@ -0,0 +1,56 @@
+/* Copyright (c) 2018 Garrett Berg, vitiral@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+ * http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+ * http://opensource.org/licenses/MIT>, at your option. This file may not be
+ * copied, modified, or distributed except according to those terms.
+ */
+//! Wrapping tests
+
+#[macro_use]
+extern crate pretty_assertions;
+
+extern crate termstyle;
+
+use termstyle::*;
+
+#[test]
+fn wrap_paragraphs() {
+    let texts = vec![
+        Text::new("Usage: a tool which does a number of things\n".into()),
+        Text::new("averyveryverylongword and short\n\nend".into()),
+    ];
+    let expected = "  Usage: a tool which
+      does a number of
+      things
+  averyveryverylongwor
+      d and short
+
+  end
+";
+    let mut result: Vec<u8> = Vec::new();
+    Wrap::new(22)
+        .indent(2)
+        .hanging(6)
+        .paint(&mut result, &texts)
+        .unwrap();
+    assert_eq!(expected, String::from_utf8(result).unwrap());
+}
+
+#[cfg(unix)]
+#[test]
+fn wrap_keeps_style() {
+    let texts = vec![
+        Text::new("one ".into()),
+        Text::new("two three".into()).bold(),
+        Text::new(" four".into()),
+    ];
+    let mut result: Vec<u8> = Vec::new();
+    Wrap::new(10).justify(true).paint(&mut result, &texts).unwrap();
+    let expected = b"\
+one    \x1b[1mtwo\x1b[0m\n\
+\x1b[1mthree\x1b[0m four\n\
+";
+    let (repr_e, repr_r) = eprint_diff(expected, &result);
+    assert_eq!(repr_e, repr_r);
+}